@@ -3,6 +3,21 @@ pub use sea_orm_migration::prelude::*;
 mod m20220101_000001_create_table;
 mod m20241218_150500_create_account_goals_table;
 mod m20241222_135418_create_bridge_goals_table;
+mod m20250110_120000_create_tx_history_table;
+mod m20250114_093000_create_vault_table;
+mod m20250116_100000_add_account_label;
+mod m20250118_110000_create_tag_tables;
+mod m20250120_090000_add_swap_volume;
+mod m20250122_100000_create_action_progress_table;
+mod m20250124_090000_create_scenario_progress_table;
+mod m20250126_090000_create_plan_tables;
+mod m20250128_090000_add_account_next_action_at;
+mod m20250130_090000_add_bridge_skip_reason;
+mod m20250201_090000_add_bridge_deposit_tracking;
+mod m20250203_090000_add_bridge_nonce;
+mod m20250205_090000_add_plan_scenario;
+mod m20250207_090000_create_bridge_deposit_tx_table;
+mod m20250209_090000_backfill_bridge_status;
 
 pub struct Migrator;
 
@@ -13,6 +28,21 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000001_create_table::Migration),
             Box::new(m20241218_150500_create_account_goals_table::Migration),
             Box::new(m20241222_135418_create_bridge_goals_table::Migration),
+            Box::new(m20250110_120000_create_tx_history_table::Migration),
+            Box::new(m20250114_093000_create_vault_table::Migration),
+            Box::new(m20250116_100000_add_account_label::Migration),
+            Box::new(m20250118_110000_create_tag_tables::Migration),
+            Box::new(m20250120_090000_add_swap_volume::Migration),
+            Box::new(m20250122_100000_create_action_progress_table::Migration),
+            Box::new(m20250124_090000_create_scenario_progress_table::Migration),
+            Box::new(m20250126_090000_create_plan_tables::Migration),
+            Box::new(m20250128_090000_add_account_next_action_at::Migration),
+            Box::new(m20250130_090000_add_bridge_skip_reason::Migration),
+            Box::new(m20250201_090000_add_bridge_deposit_tracking::Migration),
+            Box::new(m20250203_090000_add_bridge_nonce::Migration),
+            Box::new(m20250205_090000_add_plan_scenario::Migration),
+            Box::new(m20250207_090000_create_bridge_deposit_tx_table::Migration),
+            Box::new(m20250209_090000_backfill_bridge_status::Migration),
         ]
     }
 }
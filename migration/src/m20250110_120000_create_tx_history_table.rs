@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TxHistory::Table)
+                    .if_not_exists()
+                    .col(pk_auto(TxHistory::Id).integer().not_null())
+                    .col(integer(TxHistory::AccountId))
+                    .col(string(TxHistory::Chain))
+                    .col(string(TxHistory::Action))
+                    .col(string_null(TxHistory::TxHash))
+                    .col(string_null(TxHistory::TokenIn))
+                    .col(string_null(TxHistory::TokenOut))
+                    .col(string_null(TxHistory::AmountIn))
+                    .col(string_null(TxHistory::AmountOut))
+                    .col(string_null(TxHistory::Fee))
+                    .col(string(TxHistory::Status))
+                    .col(string_null(TxHistory::Error))
+                    .col(timestamp_with_time_zone(TxHistory::CreatedAt))
+                    .col(timestamp_with_time_zone(TxHistory::UpdatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-tx-history-account_id")
+                            .from(TxHistory::Table, TxHistory::AccountId)
+                            .to(Account::Table, Account::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-tx-history-account_id")
+                    .table(TxHistory::Table)
+                    .col(TxHistory::AccountId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(TxHistory::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TxHistory {
+    Table,
+    Id,
+    AccountId,
+    Chain,
+    Action,
+    TxHash,
+    TokenIn,
+    TokenOut,
+    AmountIn,
+    AmountOut,
+    Fee,
+    Status,
+    Error,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,33 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Vault::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Vault::Id).integer().not_null())
+                    .col(string(Vault::Salt))
+                    .col(string(Vault::Check))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(Vault::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Vault {
+    Table,
+    Id,
+    Salt,
+    Check,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Tag::Id).integer().not_null())
+                    .col(string(Tag::Name).unique_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountTag::Table)
+                    .if_not_exists()
+                    .col(integer(AccountTag::AccountId))
+                    .col(integer(AccountTag::TagId))
+                    .primary_key(Index::create().col(AccountTag::AccountId).col(AccountTag::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-tag-account_id")
+                            .from(AccountTag::Table, AccountTag::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-account-tag-tag_id")
+                            .from(AccountTag::Table, AccountTag::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(AccountTag::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Tag::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum AccountTag {
+    Table,
+    AccountId,
+    TagId,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Account::Table)
+                    .add_column(
+                        ColumnDef::new(Account::SwapVolumeUsd).double().not_null().default(0.0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AccountGoal::Table)
+                    .add_column(ColumnDef::new(AccountGoal::VolumeUsd).double())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AccountGoal::Table)
+                    .drop_column(AccountGoal::VolumeUsd)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter().table(Account::Table).drop_column(Account::SwapVolumeUsd).to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Account {
+    Table,
+    SwapVolumeUsd,
+}
+
+#[derive(DeriveIden)]
+enum AccountGoal {
+    Table,
+    VolumeUsd,
+}
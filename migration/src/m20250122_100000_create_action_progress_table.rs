@@ -0,0 +1,124 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Per-action counters that used to live in the `account` and `account_goal` columns
+const ACTIONS: [(&str, &str); 2] =
+    [("lifinity_swap", "swap_count"), ("underdog_create", "create_count")];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ActionProgress::Table)
+                    .if_not_exists()
+                    .col(integer(ActionProgress::AccountId))
+                    .col(string(ActionProgress::ActionName))
+                    .col(integer(ActionProgress::Target))
+                    .col(integer(ActionProgress::Done))
+                    .primary_key(
+                        Index::create()
+                            .col(ActionProgress::AccountId)
+                            .col(ActionProgress::ActionName),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-action-progress-account_id")
+                            .from(ActionProgress::Table, ActionProgress::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        for (action, column) in ACTIONS {
+            db.execute_unprepared(&format!(
+                "INSERT INTO action_progress (account_id, action_name, target, done) \
+                 SELECT account.id, '{action}', COALESCE(account_goal.{column}, 0), \
+                 account.{column} FROM account \
+                 LEFT JOIN account_goal ON account_goal.id = account.id;"
+            ))
+            .await?;
+        }
+
+        // SQLite only accepts a single column change per statement
+        for column in [Account::SwapCount, Account::CreateCount] {
+            manager
+                .alter_table(Table::alter().table(Account::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        for column in [AccountGoal::SwapCount, AccountGoal::CreateCount] {
+            manager
+                .alter_table(
+                    Table::alter().table(AccountGoal::Table).drop_column(column).to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Account::SwapCount, Account::CreateCount] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Account::Table)
+                        .add_column(ColumnDef::new(column).integer().not_null().default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        for column in [AccountGoal::SwapCount, AccountGoal::CreateCount] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AccountGoal::Table)
+                        .add_column(ColumnDef::new(column).integer().not_null().default(0))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        let db = manager.get_connection();
+
+        for (action, column) in ACTIONS {
+            for (table, value) in [("account", "done"), ("account_goal", "target")] {
+                db.execute_unprepared(&format!(
+                    "UPDATE {table} SET {column} = COALESCE((SELECT {value} FROM action_progress \
+                     WHERE action_progress.account_id = {table}.id \
+                     AND action_progress.action_name = '{action}'), 0);"
+                ))
+                .await?;
+            }
+        }
+
+        manager.drop_table(Table::drop().table(ActionProgress::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ActionProgress {
+    Table,
+    AccountId,
+    ActionName,
+    Target,
+    Done,
+}
+
+#[derive(DeriveIden)]
+enum AccountGoal {
+    Table,
+    SwapCount,
+    CreateCount,
+}
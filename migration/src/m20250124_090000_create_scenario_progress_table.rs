@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ScenarioProgress::Table)
+                    .if_not_exists()
+                    .col(integer(ScenarioProgress::AccountId).primary_key())
+                    .col(string(ScenarioProgress::Scenario))
+                    .col(integer(ScenarioProgress::Step))
+                    .col(integer_null(ScenarioProgress::RunsLeft))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-scenario-progress-account_id")
+                            .from(ScenarioProgress::Table, ScenarioProgress::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(ScenarioProgress::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ScenarioProgress {
+    Table,
+    AccountId,
+    Scenario,
+    Step,
+    RunsLeft,
+}
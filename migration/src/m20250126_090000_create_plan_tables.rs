@@ -0,0 +1,91 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Plan::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Plan::Id).integer().not_null())
+                    .col(timestamp_with_time_zone(Plan::CreatedAt))
+                    .col(timestamp_with_time_zone_null(Plan::ApprovedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PlannedAction::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PlannedAction::Id).integer().not_null())
+                    .col(integer(PlannedAction::PlanId))
+                    .col(integer(PlannedAction::AccountId))
+                    .col(integer(PlannedAction::Step))
+                    .col(string(PlannedAction::ActionName))
+                    .col(string(PlannedAction::Params))
+                    .col(timestamp_with_time_zone(PlannedAction::ScheduledAt))
+                    .col(timestamp_with_time_zone_null(PlannedAction::ExecutedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-planned-action-plan_id")
+                            .from(PlannedAction::Table, PlannedAction::PlanId)
+                            .to(Plan::Table, Plan::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-planned-action-account_id")
+                            .from(PlannedAction::Table, PlannedAction::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-planned-action-plan_id-account_id")
+                    .table(PlannedAction::Table)
+                    .col(PlannedAction::PlanId)
+                    .col(PlannedAction::AccountId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(PlannedAction::Table).to_owned()).await?;
+        manager.drop_table(Table::drop().table(Plan::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Plan {
+    Table,
+    Id,
+    CreatedAt,
+    ApprovedAt,
+}
+
+#[derive(DeriveIden)]
+enum PlannedAction {
+    Table,
+    Id,
+    PlanId,
+    AccountId,
+    Step,
+    ActionName,
+    Params,
+    ScheduledAt,
+    ExecutedAt,
+}
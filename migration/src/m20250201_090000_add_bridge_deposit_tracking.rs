@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = [
+            string_null(BridgeModuleState::Status),
+            string_null(BridgeModuleState::TxHash),
+            string_null(BridgeModuleState::Amount),
+            big_integer_null(BridgeModuleState::EclipseBalanceBefore),
+            timestamp_with_time_zone_null(BridgeModuleState::SubmittedAt),
+            timestamp_with_time_zone_null(BridgeModuleState::ConfirmedAt),
+            timestamp_with_time_zone_null(BridgeModuleState::ArrivedAt),
+        ];
+
+        // SQLite can only add a single column per statement
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter().table(BridgeModuleState::Table).add_column(column).to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let columns = [
+            BridgeModuleState::Status,
+            BridgeModuleState::TxHash,
+            BridgeModuleState::Amount,
+            BridgeModuleState::EclipseBalanceBefore,
+            BridgeModuleState::SubmittedAt,
+            BridgeModuleState::ConfirmedAt,
+            BridgeModuleState::ArrivedAt,
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter().table(BridgeModuleState::Table).drop_column(column).to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum BridgeModuleState {
+    Table,
+    Status,
+    TxHash,
+    Amount,
+    EclipseBalanceBefore,
+    SubmittedAt,
+    ConfirmedAt,
+    ArrivedAt,
+}
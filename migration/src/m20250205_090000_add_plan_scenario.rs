@@ -0,0 +1,35 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite can only add a single column per statement
+        for column in [string_null(Plan::Scenario), integer_null(Plan::ScenarioSteps)] {
+            manager
+                .alter_table(Table::alter().table(Plan::Table).add_column(column).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Plan::Scenario, Plan::ScenarioSteps] {
+            manager
+                .alter_table(Table::alter().table(Plan::Table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Plan {
+    Table,
+    Scenario,
+    ScenarioSteps,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use crate::m20220101_000001_create_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BridgeDepositTx::Table)
+                    .if_not_exists()
+                    .col(string(BridgeDepositTx::TxHash).primary_key())
+                    .col(integer(BridgeDepositTx::AccountId))
+                    .col(big_integer(BridgeDepositTx::Nonce))
+                    .col(timestamp_with_time_zone(BridgeDepositTx::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bridge-deposit-tx-account_id")
+                            .from(BridgeDepositTx::Table, BridgeDepositTx::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-bridge-deposit-tx-account_id-nonce")
+                    .table(BridgeDepositTx::Table)
+                    .col(BridgeDepositTx::AccountId)
+                    .col(BridgeDepositTx::Nonce)
+                    .to_owned(),
+            )
+            .await?;
+
+        // the deposits recorded so far only kept their last hash
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO bridge_deposit_tx (tx_hash, account_id, nonce, created_at) \
+                 SELECT tx_hash, id, nonce, COALESCE(submitted_at, CURRENT_TIMESTAMP) \
+                 FROM bridge_module_state WHERE tx_hash IS NOT NULL AND nonce IS NOT NULL;",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(Table::drop().table(BridgeDepositTx::Table).to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BridgeDepositTx {
+    Table,
+    TxHash,
+    AccountId,
+    Nonce,
+    CreatedAt,
+}
@@ -0,0 +1,34 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // accounts bridged before the deposits were tracked have no status, while the warmup with
+        // `REQUIRE_BRIDGE_ARRIVAL` only runs the arrived ones
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE bridge_module_state SET status = 'arrived_l2' \
+                 WHERE funds_bridged = 1 AND status IS NULL;",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // a tracked deposit always has a hash or an arrival time
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE bridge_module_state SET status = NULL \
+                 WHERE status = 'arrived_l2' AND tx_hash IS NULL AND arrived_at IS NULL;",
+            )
+            .await?;
+
+        Ok(())
+    }
+}
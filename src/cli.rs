@@ -0,0 +1,263 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    config::{Config, CONFIG_FILE_PATH},
+    modules::warmup::plan::PLAN_EXPORT_FILE_PATH,
+};
+
+#[derive(Parser)]
+#[command(version, about = "Eclipse farmer. Runs the interactive menu when no command is given")]
+pub struct Cli {
+    /// Path to the config file
+    #[arg(long, global = true, default_value = CONFIG_FILE_PATH)]
+    pub config: PathBuf,
+
+    #[command(flatten)]
+    pub overrides: ConfigOverrides,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Manage the accounts database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Change the warmup goals of existing accounts
+    Goals {
+        #[command(subcommand)]
+        command: GoalsCommand,
+    },
+    /// Bridge funds from Ethereum mainnet to Eclipse
+    Bridge,
+    /// Precompute the warmup actions for review before they are run
+    Plan {
+        #[command(subcommand)]
+        command: PlanCommand,
+    },
+    /// Run warmup actions on Eclipse
+    Warmup,
+    /// Print the progress of every account
+    Status,
+    /// Print every recorded on-chain action and the fees paid
+    History {
+        /// Only show the actions of this account
+        #[arg(long)]
+        account_id: Option<i32>,
+    },
+}
+
+impl Command {
+    /// Whether the command decrypts private keys and needs the vault to be unlocked
+    pub fn requires_keys(&self) -> bool {
+        !matches!(
+            self,
+            Self::Goals { .. } | Self::Plan { .. } | Self::Status | Self::History { .. }
+        )
+    }
+}
+
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Erase the database and import accounts from the data files
+    Generate,
+    /// Import accounts from the data files into the existing database
+    Append,
+    /// Import accounts from a CSV or JSON file with one row per account
+    Import {
+        /// Path to the `.csv` or `.json` file
+        path: PathBuf,
+
+        /// Erase the database before importing
+        #[arg(long)]
+        erase: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GoalsCommand {
+    /// Raise the goals by a random amount from the config ranges
+    TopUp,
+    /// Roll new goals from the config ranges, keeping the progress
+    Reroll,
+}
+
+#[derive(Subcommand)]
+pub enum PlanCommand {
+    /// Plan every action the active accounts have left, replacing the previous plan
+    Generate,
+    /// Write the plan to a CSV file for review
+    Export {
+        /// Path to the `.csv` file
+        #[arg(long, default_value = PLAN_EXPORT_FILE_PATH)]
+        path: PathBuf,
+    },
+    /// Let the warmup run the plan
+    Approve,
+    /// Delete the plan, the warmup goes back to deciding every action on the fly
+    Discard,
+}
+
+/// Values that take precedence over the ones read from the config file
+#[derive(Args)]
+pub struct ConfigOverrides {
+    /// Overrides `general.ECLIPSE_RPC_URL`
+    #[arg(long, global = true)]
+    pub eclipse_rpc_url: Option<String>,
+
+    /// Overrides `general.MAINNET_RPC_URL`
+    #[arg(long, global = true)]
+    pub mainnet_rpc_url: Option<String>,
+
+    /// Overrides `general.THREAD_COUNT`
+    #[arg(long, global = true)]
+    pub thread_count: Option<u64>,
+
+    /// Simulate every transaction instead of sending it, overrides `general.DRY_RUN`
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Keep running and pick up new or due accounts instead of exiting, overrides `general.DAEMON`
+    #[arg(long, global = true)]
+    pub daemon: bool,
+
+    /// Only run the accounts with this tag and tag the imported ones, overrides `general.TAG`
+    #[arg(long, global = true)]
+    pub tag: Option<String>,
+
+    /// Path to a scenario file for the warmup, overrides `general.SCENARIO`
+    #[arg(long, global = true, value_name = "PATH")]
+    pub scenario: Option<String>,
+
+    /// Overrides `bridge.BALANCE_PERCENTAGE_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub bridge_balance_percentage_range: Option<[u32; 2]>,
+
+    /// Overrides `bridge.WALLET_SLEEP_DELAY_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub bridge_sleep_range: Option<[u32; 2]>,
+
+    /// Overrides `bridge.MAX_BASE_FEE_GWEI`
+    #[arg(long, global = true, value_name = "GWEI")]
+    pub max_base_fee: Option<f64>,
+
+    /// Overrides `bridge.MAX_PRIORITY_FEE_GWEI`
+    #[arg(long, global = true, value_name = "GWEI")]
+    pub max_priority_fee: Option<f64>,
+
+    /// Overrides `lifinity.SWAPS_COUNT_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub lifinity_swaps_count_range: Option<[u32; 2]>,
+
+    /// Overrides `lifinity.BALANCE_PERCENTAGE_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub lifinity_balance_percentage_range: Option<[u32; 2]>,
+
+    /// Overrides `lifinity.WALLET_SLEEP_DELAY_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub lifinity_sleep_range: Option<[u32; 2]>,
+
+    /// Overrides `lifinity.WEIGHT`
+    #[arg(long, global = true)]
+    pub lifinity_weight: Option<u32>,
+
+    /// Overrides `underdog.CREATE_COUNT_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub underdog_create_count_range: Option<[u32; 2]>,
+
+    /// Overrides `underdog.WALLET_SLEEP_DELAY_RANGE`, passed as `MIN-MAX`
+    #[arg(long, global = true, value_name = "MIN-MAX", value_parser = parse_range)]
+    pub underdog_sleep_range: Option<[u32; 2]>,
+
+    /// Overrides `underdog.WEIGHT`
+    #[arg(long, global = true)]
+    pub underdog_weight: Option<u32>,
+
+    /// Overrides `schedule.SPREAD_DAYS`
+    #[arg(long, global = true)]
+    pub spread_days: Option<u32>,
+
+    /// Overrides `schedule.MAX_ACTIONS_PER_DAY`
+    #[arg(long, global = true)]
+    pub max_actions_per_day: Option<u32>,
+}
+
+impl ConfigOverrides {
+    pub fn apply(self, config: &mut Config) {
+        if let Some(url) = self.eclipse_rpc_url {
+            config.general.eclipse_rpc_url = url;
+        }
+        if let Some(url) = self.mainnet_rpc_url {
+            config.general.mainnet_rpc_url = url;
+        }
+        if let Some(count) = self.thread_count {
+            config.general.thread_count = count;
+        }
+        if self.dry_run {
+            config.general.dry_run = true;
+        }
+        if self.daemon {
+            config.general.daemon = true;
+        }
+        if let Some(tag) = self.tag {
+            config.general.tag = Some(tag);
+        }
+        if let Some(path) = self.scenario {
+            config.general.scenario = Some(path);
+        }
+        if let Some(range) = self.bridge_balance_percentage_range {
+            config.bridge.balance_percentage_range = range;
+        }
+        if let Some(range) = self.bridge_sleep_range {
+            config.bridge.wallet_sleep_delay_range = range;
+        }
+        if let Some(fee) = self.max_base_fee {
+            config.bridge.max_base_fee_gwei = Some(fee);
+        }
+        if let Some(fee) = self.max_priority_fee {
+            config.bridge.max_priority_fee_gwei = Some(fee);
+        }
+        if let Some(range) = self.lifinity_swaps_count_range {
+            config.lifinity.swaps_count_range = range;
+        }
+        if let Some(range) = self.lifinity_balance_percentage_range {
+            config.lifinity.balance_percentage_range = range;
+        }
+        if let Some(range) = self.lifinity_sleep_range {
+            config.lifinity.wallet_sleep_delay_range = range;
+        }
+        if let Some(weight) = self.lifinity_weight {
+            config.lifinity.weight = weight;
+        }
+        if let Some(range) = self.underdog_create_count_range {
+            config.underdog.create_count_range = range;
+        }
+        if let Some(range) = self.underdog_sleep_range {
+            config.underdog.wallet_sleep_delay_range = Some(range);
+        }
+        if let Some(weight) = self.underdog_weight {
+            config.underdog.weight = weight;
+        }
+        if let Some(days) = self.spread_days {
+            config.schedule.spread_days = Some(days);
+        }
+        if let Some(count) = self.max_actions_per_day {
+            config.schedule.max_actions_per_day = Some(count);
+        }
+    }
+}
+
+fn parse_range(value: &str) -> Result<[u32; 2], String> {
+    let (min, max) =
+        value.split_once('-').ok_or_else(|| format!("Expected `MIN-MAX`, got `{value}`"))?;
+
+    let min = min.trim().parse::<u32>().map_err(|e| format!("Invalid range start `{min}`: {e}"))?;
+    let max = max.trim().parse::<u32>().map_err(|e| format!("Invalid range end `{max}`: {e}"))?;
+
+    Ok([min, max])
+}
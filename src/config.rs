@@ -1,8 +1,9 @@
-use serde::Deserialize;
-use std::path::Path;
+use chrono::{FixedOffset, NaiveTime};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer};
+use std::{ops::RangeInclusive, path::Path, str::FromStr};
 
-#[allow(unused)]
-const CONFIG_FILE_PATH: &str = "data/config.toml";
+pub const CONFIG_FILE_PATH: &str = "data/config.toml";
 
 #[derive(Deserialize)]
 pub struct Config {
@@ -10,6 +11,10 @@ pub struct Config {
     pub bridge: Bridge,
     pub lifinity: Lifinity,
     pub underdog: Underdog,
+    #[serde(default)]
+    pub import: Import,
+    #[serde(default)]
+    pub schedule: Schedule,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,36 +23,234 @@ pub struct General {
     pub eclipse_rpc_url: String,
     pub mainnet_rpc_url: String,
     pub thread_count: u64,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default = "default_retry_backoff_secs")]
+    pub retry_backoff_secs: u64,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Path to a scenario file, warmup follows it instead of picking random actions
+    #[serde(default)]
+    pub scenario: Option<String>,
+    /// Keep the mode running and pick up accounts that become pending instead of exiting
+    #[serde(default)]
+    pub daemon: bool,
+    /// Warmup only runs accounts whose bridge deposit arrived on Eclipse
+    #[serde(default)]
+    pub require_bridge_arrival: bool,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub struct Bridge {
+    #[serde(default)]
+    pub amount_strategy: AmountStrategy,
+    /// Used by the `percentage` strategy
     pub balance_percentage_range: [u32; 2],
+    /// ETH, used by the `fixed` strategy
+    #[serde(default)]
+    pub fixed_amount_range: Option<[f64; 2]>,
+    /// ETH left on mainnet for gas, used by the `all_but_reserve` strategy
+    #[serde(default)]
+    pub reserve: Option<f64>,
+    /// ETH the Eclipse wallet should end up with, used by the `target_balance` strategy
+    #[serde(default)]
+    pub target_eclipse_balance: Option<f64>,
+    /// Gwei, the bridge waits while the base fee is higher
+    #[serde(default)]
+    pub max_base_fee_gwei: Option<f64>,
+    /// Gwei, the bridge waits while the priority fee is higher
+    #[serde(default)]
+    pub max_priority_fee_gwei: Option<f64>,
+    /// Longest a wallet waits for the fees to drop before it is left for the next run, forever
+    /// when not set
+    #[serde(default)]
+    pub gas_wait_timeout_secs: Option<u64>,
+    /// A deposit that hasn't shown up on Eclipse this long after its receipt is marked as stuck
+    #[serde(default = "default_arrival_timeout_secs")]
+    pub arrival_timeout_secs: u64,
     pub wallet_sleep_delay_range: [u32; 2],
 }
 
+/// How the amount to bridge is picked
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountStrategy {
+    /// A random share of the mainnet balance
+    #[default]
+    Percentage,
+    /// A random amount of ETH
+    Fixed,
+    /// The whole balance but a reserve for gas
+    AllButReserve,
+    /// Whatever the Eclipse wallet lacks to reach a balance
+    TargetBalance,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub struct Lifinity {
     pub swaps_count_range: [u32; 2],
+    #[serde(default)]
+    pub volume_usd_range: Option<[f64; 2]>,
     pub balance_percentage_range: [u32; 2],
     pub wallet_sleep_delay_range: [u32; 2],
+    #[serde(default = "default_weight")]
+    pub weight: u32,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub struct Underdog {
     pub create_count_range: [u32; 2],
+    /// Falls back to `lifinity.WALLET_SLEEP_DELAY_RANGE` when not set
+    #[serde(default)]
+    pub wallet_sleep_delay_range: Option<[u32; 2]>,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// Spreads the warmup of every account over time, actions run back to back when nothing is set
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub struct Schedule {
+    /// Days the goals of an account are spread over
+    #[serde(default)]
+    pub spread_days: Option<u32>,
+    /// Upper bound of actions per account in 24 hours
+    #[serde(default)]
+    pub max_actions_per_day: Option<u32>,
+    /// Windows of the day the modes run in, any time of the day when empty
+    #[serde(default)]
+    pub active_hours: Vec<TimeWindow>,
+    /// IANA zone the active hours are given in, e.g. `Europe/Berlin`. Follows daylight saving time
+    /// and takes precedence over `UTC_OFFSET`
+    #[serde(default)]
+    pub timezone: Option<Tz>,
+    /// Fixed offset the active hours are given in, it doesn't follow daylight saving time. UTC
+    /// when neither this nor `TIMEZONE` is set
+    #[serde(default, deserialize_with = "deserialize_utc_offset")]
+    pub utc_offset: Option<FixedOffset>,
+}
+
+/// `HH:MM-HH:MM`, a window whose end is before its start goes past midnight. The start and the end
+/// can't be equal
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TimeWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match self.start <= self.end {
+            true => self.start <= time && time < self.end,
+            false => time >= self.start || time < self.end,
+        }
+    }
+}
+
+impl TryFrom<String> for TimeWindow {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("invalid time window `{value}`, expected `HH:MM-HH:MM`");
+
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").map_err(|_| invalid())?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").map_err(|_| invalid())?;
+
+        if start == end {
+            return Err(format!("empty time window `{value}`, the start and the end are equal"));
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+/// Missing keys fall back to the default MetaMask and Phantom paths with a single account per
+/// mnemonic
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", default)]
+pub struct Import {
+    pub evm_derivation_path: String,
+    pub eclipse_derivation_path: String,
+    /// Both ends included, the start can't be after the end
+    #[serde(deserialize_with = "deserialize_index_range")]
+    pub mnemonic_index_range: [u32; 2],
+}
+
+impl Default for Import {
+    fn default() -> Self {
+        Self {
+            evm_derivation_path: "m/44'/60'/0'/0/{index}".to_string(),
+            eclipse_derivation_path: "m/44'/501'/{index}'/0'".to_string(),
+            mnemonic_index_range: [0, 0],
+        }
+    }
+}
+
+impl Import {
+    /// `{index}` values every mnemonic is derived at, one wallet each
+    pub fn mnemonic_indexes(&self) -> RangeInclusive<u32> {
+        let [from, to] = self.mnemonic_index_range;
+
+        from..=to
+    }
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_backoff_secs() -> u64 {
+    30
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_arrival_timeout_secs() -> u64 {
+    3600
+}
+
+fn deserialize_utc_offset<'de, D>(deserializer: D) -> Result<Option<FixedOffset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|offset| {
+            FixedOffset::from_str(&offset).map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "invalid UTC offset `{offset}`, expected `+HH:MM` or `-HH:MM`"
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn deserialize_index_range<'de, D>(deserializer: D) -> Result<[u32; 2], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let [from, to] = <[u32; 2]>::deserialize(deserializer)?;
+
+    if from > to {
+        return Err(serde::de::Error::custom(format!(
+            "`import.MNEMONIC_INDEX_RANGE` starts at {from} after it ends at {to}"
+        )));
+    }
+
+    Ok([from, to])
 }
 
 impl Config {
-    async fn read_from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+    pub async fn read_from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
         let cfg_str = tokio::fs::read_to_string(path).await?;
         Ok(toml::from_str(&cfg_str)?)
     }
-
-    pub async fn read_default() -> Self {
-        Self::read_from_file(CONFIG_FILE_PATH).await.expect("Default config to be valid")
-    }
 }
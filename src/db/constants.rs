@@ -2,3 +2,5 @@ pub const DEFAULT_DB_URL: &str = "sqlite://data/database.sqlite?mode=rwc";
 pub const EVM_PRIVATE_KEYS_FILE_PATH: &str = "data/evm_private_keys.txt";
 pub const ECLIPSE_PRIVATE_KEYS_FILE_PATH: &str = "data/eclipse_private_keys.txt";
 pub const PROXIES_FILE_PATH: &str = "data/proxies.txt";
+pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "ECLIPSE_FARMER_KEYSTORE_PASSWORD";
+pub const PASSPHRASE_ENV_VAR: &str = "ECLIPSE_FARMER_PASSPHRASE";
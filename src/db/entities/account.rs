@@ -2,39 +2,55 @@
 
 use std::{str::FromStr, sync::Arc};
 
-use crate::{modules::warmup::action::WarmupAction, onchain::evm::client::EvmClient};
+use crate::{
+    onchain::evm::client::EvmClient,
+    utils::{crypto::cipher, secret::Secret},
+};
 use alloy::{network::Ethereum, providers::Provider, transports::Transport};
 use alloy_chains::NamedChain;
-use rand::{thread_rng, Rng};
 use reqwest::Proxy;
 use sea_orm::entity::prelude::*;
 use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "account")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub is_active: bool,
     #[sea_orm(unique)]
-    pub evm_private_key: String,
+    pub evm_private_key: Secret,
     #[sea_orm(unique)]
     pub evm_address: String,
     #[sea_orm(unique)]
-    pub eclipse_private_key: String,
+    pub eclipse_private_key: Secret,
     #[sea_orm(unique)]
     pub eclipse_address: String,
     pub proxy: Option<String>,
-    pub swap_count: i32,
-    pub create_count: i32,
+    pub label: Option<String>,
+    #[sea_orm(column_type = "Double")]
+    pub swap_volume_usd: f64,
+    pub next_action_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::account_goal::Entity")]
     AccountGoal,
+    #[sea_orm(has_many = "super::account_tag::Entity")]
+    AccountTag,
+    #[sea_orm(has_many = "super::action_progress::Entity")]
+    ActionProgress,
+    #[sea_orm(has_many = "super::bridge_deposit_tx::Entity")]
+    BridgeDepositTx,
     #[sea_orm(has_many = "super::bridge_module_state::Entity")]
     BridgeModuleState,
+    #[sea_orm(has_many = "super::planned_action::Entity")]
+    PlannedAction,
+    #[sea_orm(has_one = "super::scenario_progress::Entity")]
+    ScenarioProgress,
+    #[sea_orm(has_many = "super::tx_history::Entity")]
+    TxHistory,
 }
 
 impl Related<super::account_goal::Entity> for Entity {
@@ -43,55 +59,82 @@ impl Related<super::account_goal::Entity> for Entity {
     }
 }
 
+impl Related<super::account_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AccountTag.def()
+    }
+}
+
+impl Related<super::action_progress::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ActionProgress.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::account_tag::Relation::Tag.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::account_tag::Relation::Account.def().rev())
+    }
+}
+
+impl Related<super::bridge_deposit_tx::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BridgeDepositTx.def()
+    }
+}
+
 impl Related<super::bridge_module_state::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::BridgeModuleState.def()
     }
 }
 
+impl Related<super::planned_action::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PlannedAction.def()
+    }
+}
+
+impl Related<super::scenario_progress::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ScenarioProgress.def()
+    }
+}
+
+impl Related<super::tx_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TxHistory.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Model {
-    pub fn to_evm_client<P, T>(&self, provider: Arc<P>) -> EvmClient<P, T>
+    pub fn to_evm_client<P, T>(&self, provider: Arc<P>) -> eyre::Result<EvmClient<P, T>>
     where
         P: Provider<T, Ethereum>,
         T: Transport + Clone,
     {
-        EvmClient::new(provider, &self.evm_private_key, NamedChain::Mainnet)
+        let private_key = cipher()?.decrypt(self.evm_private_key.expose())?;
+
+        Ok(EvmClient::new(provider, &private_key, NamedChain::Mainnet))
     }
 
     pub fn eclipse_pubkey(&self) -> Pubkey {
         Pubkey::from_str(&self.eclipse_address).unwrap()
     }
 
-    pub fn eclise_keypair(&self) -> Keypair {
-        Keypair::from_base58_string(&self.eclipse_private_key)
+    pub fn eclise_keypair(&self) -> eyre::Result<Keypair> {
+        let private_key = cipher()?.decrypt(self.eclipse_private_key.expose())?;
+
+        Ok(Keypair::from_base58_string(private_key.expose()))
     }
 
     pub fn proxy(&self) -> Option<Proxy> {
         self.proxy.as_ref().map(|p| Proxy::all(p).unwrap())
     }
-
-    pub fn get_random_warmup_action(
-        &self,
-        goal: super::account_goal::Model,
-    ) -> Option<WarmupAction> {
-        let mut possible_actions = Vec::new();
-
-        if self.create_count < goal.create_count {
-            possible_actions.push(WarmupAction::UnderdogCreate);
-        }
-
-        if self.swap_count < goal.swap_count {
-            possible_actions.push(WarmupAction::LifinitySwap);
-        }
-
-        if possible_actions.is_empty() {
-            return None;
-        }
-
-        let mut rng = thread_rng();
-        let choice = rng.gen_range(0..possible_actions.len());
-        Some(possible_actions[choice].clone())
-    }
 }
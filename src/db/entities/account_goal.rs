@@ -2,13 +2,13 @@
 
 use sea_orm::entity::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "account_goal")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    pub swap_count: i32,
-    pub create_count: i32,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub volume_usd: Option<f64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.2
 
+use super::sea_orm_active_enums::BridgeStatus;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -8,6 +9,15 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub funds_bridged: bool,
+    pub skip_reason: Option<String>,
+    pub status: Option<BridgeStatus>,
+    pub tx_hash: Option<String>,
+    pub nonce: Option<i64>,
+    pub amount: Option<String>,
+    pub eclipse_balance_before: Option<i64>,
+    pub submitted_at: Option<DateTimeUtc>,
+    pub confirmed_at: Option<DateTimeUtc>,
+    pub arrived_at: Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -4,4 +4,14 @@ pub mod prelude;
 
 pub mod account;
 pub mod account_goal;
+pub mod account_tag;
+pub mod action_progress;
+pub mod bridge_deposit_tx;
 pub mod bridge_module_state;
+pub mod plan;
+pub mod planned_action;
+pub mod scenario_progress;
+pub mod sea_orm_active_enums;
+pub mod tag;
+pub mod tx_history;
+pub mod vault;
@@ -2,7 +2,11 @@
 
 pub use super::{
     account::Entity as Account, account_goal::Entity as AccountGoal,
-    bridge_module_state::Entity as BridgeModuleState,
+    account_tag::Entity as AccountTag, action_progress::Entity as ActionProgress,
+    bridge_deposit_tx::Entity as BridgeDepositTx, bridge_module_state::Entity as BridgeModuleState,
+    plan::Entity as Plan, planned_action::Entity as PlannedAction,
+    scenario_progress::Entity as ScenarioProgress, tag::Entity as Tag,
+    tx_history::Entity as TxHistory, vault::Entity as Vault,
 };
 
 pub use super::{
@@ -14,3 +18,27 @@ pub use super::{account::Model as AccountModel, account_goal::Model as AccountGo
 pub use super::bridge_module_state::{
     ActiveModel as BridgeStateActiveModel, Model as BridgeStateModel,
 };
+
+pub use super::bridge_deposit_tx::ActiveModel as BridgeDepositTxActiveModel;
+
+pub use super::tx_history::{ActiveModel as TxHistoryActiveModel, Model as TxHistoryModel};
+
+pub use super::tag::{ActiveModel as TagActiveModel, Model as TagModel};
+
+pub use super::account_tag::ActiveModel as AccountTagActiveModel;
+
+pub use super::action_progress::{
+    ActiveModel as ActionProgressActiveModel, Model as ActionProgressModel,
+};
+
+pub use super::scenario_progress::{
+    ActiveModel as ScenarioProgressActiveModel, Model as ScenarioProgressModel,
+};
+
+pub use super::plan::{ActiveModel as PlanActiveModel, Model as PlanModel};
+
+pub use super::planned_action::{
+    ActiveModel as PlannedActionActiveModel, Model as PlannedActionModel,
+};
+
+pub use super::vault::{ActiveModel as VaultActiveModel, Model as VaultModel};
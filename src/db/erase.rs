@@ -1,10 +1,23 @@
-use sea_orm::{ConnectionTrait, DatabaseBackend, DbConn, DbErr, Statement};
+use sea_orm::{ConnectionTrait, DatabaseBackend, DbErr, Statement};
 
-pub async fn erase_all_tables(db: &DbConn) -> Result<(), DbErr> {
+pub async fn erase_all_tables(db: &impl ConnectionTrait) -> Result<(), DbErr> {
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM tx_history;")).await?;
     db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM account_goal;"))
         .await?;
     db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM bridge_module_state;"))
         .await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM bridge_deposit_tx;"))
+        .await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM action_progress;"))
+        .await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM scenario_progress;"))
+        .await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM planned_action;"))
+        .await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM plan;")).await?;
+
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM account_tag;")).await?;
+    db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM tag;")).await?;
 
     db.execute(Statement::from_string(DatabaseBackend::Sqlite, "DELETE FROM account;")).await?;
 
@@ -19,6 +32,26 @@ pub async fn erase_all_tables(db: &DbConn) -> Result<(), DbErr> {
         "DELETE FROM sqlite_sequence WHERE name='bridge_module_state';",
     ))
     .await?;
+    db.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sqlite_sequence WHERE name='tx_history';",
+    ))
+    .await?;
+    db.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sqlite_sequence WHERE name='tag';",
+    ))
+    .await?;
+    db.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sqlite_sequence WHERE name='planned_action';",
+    ))
+    .await?;
+    db.execute(Statement::from_string(
+        DatabaseBackend::Sqlite,
+        "DELETE FROM sqlite_sequence WHERE name='plan';",
+    ))
+    .await?;
     db.execute(Statement::from_string(
         DatabaseBackend::Sqlite,
         "DELETE FROM sqlite_sequence WHERE name='account';",
@@ -1,59 +1,318 @@
-use sea_orm::{DbConn, DbErr};
+use std::{collections::HashSet, path::Path};
+
+use reqwest::{Proxy, Url};
+use sea_orm::{DbConn, TransactionTrait};
+use serde::Deserialize;
 
 use crate::{
-    config::Config,
-    db::constants::{
-        ECLIPSE_PRIVATE_KEYS_FILE_PATH, EVM_PRIVATE_KEYS_FILE_PATH, PROXIES_FILE_PATH,
+    config::{Config, Import},
+    db::{
+        constants::{
+            ECLIPSE_PRIVATE_KEYS_FILE_PATH, EVM_PRIVATE_KEYS_FILE_PATH, PROXIES_FILE_PATH,
+        },
+        erase::erase_all_tables,
+        keys::{
+            parse_eclipse_key, parse_evm_key, read_eclipse_keys, read_evm_keys, KeyEntry, ParsedKey,
+        },
     },
+    modules::warmup::{action::WarmupAction, lifinity::LifinitySwap, underdog::UnderdogCreate},
     utils::files::read_file_lines,
 };
 
-use super::service::prelude::{AccountGoalMutation, AccountMutation, BridgeModuleStateMutation};
+use super::service::prelude::{
+    AccountGoalMutation, AccountMutation, AccountQuery, BridgeModuleStateMutation, GoalOverrides,
+    TagMutation,
+};
+
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+struct ImportRow {
+    evm: ParsedKey,
+    eclipse: ParsedKey,
+    proxy: Option<String>,
+    label: Option<String>,
+    tags: Vec<String>,
+    goal: GoalOverrides,
+}
+
+/// One account of a CSV or JSON import file. Keys accept the same formats as the key files
+#[derive(Deserialize)]
+struct AccountRecord {
+    evm_private_key: String,
+    eclipse_private_key: String,
+    #[serde(default)]
+    proxy: Option<String>,
+    #[serde(default)]
+    label: Option<String>,
+    /// Comma separated
+    #[serde(default)]
+    tags: Option<String>,
+    #[serde(default)]
+    swap_count: Option<u32>,
+    #[serde(default)]
+    create_count: Option<u32>,
+    #[serde(default)]
+    volume_usd: Option<f64>,
+}
+
+#[derive(Default)]
+struct ImportReport {
+    accepted: Vec<(usize, String)>,
+    skipped: Vec<(usize, String)>,
+    invalid: Vec<(usize, String)>,
+}
+
+impl ImportReport {
+    fn print(&self) {
+        println!(
+            "\nImport report: {} accepted, {} skipped, {} invalid",
+            self.accepted.len(),
+            self.skipped.len(),
+            self.invalid.len()
+        );
 
-pub async fn generate_db(config: &Config, connection: &DbConn) -> Result<(), DbErr> {
-    let (evm_pks, eclipse_pks, proxies) = tokio::try_join!(
-        read_file_lines(EVM_PRIVATE_KEYS_FILE_PATH),
-        read_file_lines(ECLIPSE_PRIVATE_KEYS_FILE_PATH),
+        let rows = self
+            .accepted
+            .iter()
+            .map(|row| ("ACCEPTED", row))
+            .chain(self.skipped.iter().map(|row| ("SKIPPED", row)))
+            .chain(self.invalid.iter().map(|row| ("INVALID", row)));
+
+        for (status, (row, details)) in rows {
+            println!("#{row} | {status} | {details}");
+        }
+    }
+}
+
+/// Imports accounts from the key and proxy files, matched by line position. With `erase_existing`
+/// the current accounts are replaced by the imported ones
+pub async fn generate_db(
+    config: &Config,
+    connection: &DbConn,
+    erase_existing: bool,
+) -> eyre::Result<()> {
+    let (evm_keys, eclipse_keys, proxies) = tokio::try_join!(
+        read_evm_keys(EVM_PRIVATE_KEYS_FILE_PATH, &config.import),
+        read_eclipse_keys(ECLIPSE_PRIVATE_KEYS_FILE_PATH, &config.import),
         read_file_lines(PROXIES_FILE_PATH)
-    )
-    .map_err(|e| DbErr::Custom(format!("Failed to read files: {e}")))?;
+    )?;
 
-    assert_eq!(
-        evm_pks.len(),
-        eclipse_pks.len(),
-        "Amount of EVM and Eclipse private keys is not equal"
-    );
+    if evm_keys.len() != eclipse_keys.len() {
+        eyre::bail!(
+            "Amount of EVM ({}) and Eclipse ({}) wallets is not equal, nothing was imported",
+            evm_keys.len(),
+            eclipse_keys.len()
+        );
+    }
 
     let mut proxies_iter = proxies.into_iter();
 
-    for (evm_pk, eclipse_pk) in evm_pks.into_iter().zip(eclipse_pks.into_iter()) {
-        let maybe_proxy = proxies_iter.next();
+    let rows = evm_keys
+        .into_iter()
+        .zip(eclipse_keys)
+        .map(|(evm, eclipse)| validate_key_entries(evm, eclipse, proxies_iter.next()))
+        .collect();
 
-        match AccountMutation::create_account(&evm_pk, &eclipse_pk, maybe_proxy, connection).await {
-            Ok(account_id) => {
-                AccountGoalMutation::create_account_goal(config, account_id, connection).await?;
-                BridgeModuleStateMutation::create_account_bridge_state(account_id, connection)
-                    .await?;
-            }
+    if proxies_iter.len() > 0 {
+        tracing::warn!("There are {} unused proxy entries left in the file.", proxies_iter.len());
+    }
+
+    import_rows(config, connection, rows, erase_existing).await
+}
+
+/// Imports accounts from a CSV file with a header row or a JSON array of objects. Both use the
+/// `evm_private_key`, `eclipse_private_key`, `proxy`, `label`, `tags`, `swap_count`,
+/// `create_count` and `volume_usd` fields, only the keys are required
+pub async fn import_accounts_file(
+    config: &Config,
+    connection: &DbConn,
+    path: impl AsRef<Path>,
+    erase_existing: bool,
+) -> eyre::Result<()> {
+    let path = path.as_ref();
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| eyre::eyre!("Failed to read `{}`: {e}", path.display()))?;
+
+    let records = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => csv::Reader::from_reader(contents.as_bytes())
+            .deserialize::<AccountRecord>()
+            .map(|record| record.map_err(|e| e.to_string()))
+            .collect::<Vec<_>>(),
+        Some("json") => serde_json::from_str::<Vec<AccountRecord>>(&contents)
+            .map_err(|e| eyre::eyre!("Failed to parse `{}`: {e}", path.display()))?
+            .into_iter()
+            .map(Ok)
+            .collect(),
+        _ => eyre::bail!("Unsupported import file `{}`, expected .csv or .json", path.display()),
+    };
+
+    let rows = records
+        .into_iter()
+        .map(|record| record.and_then(|record| validate_record(record, &config.import)))
+        .collect();
+
+    import_rows(config, connection, rows, erase_existing).await
+}
+
+/// Prints the preflight report and, when every row is valid, inserts the ones that are not in the
+/// database yet in a single transaction. Imported accounts also get the `general.TAG` tag when it is set
+async fn import_rows(
+    config: &Config,
+    connection: &DbConn,
+    rows: Vec<Result<ImportRow, String>>,
+    erase_existing: bool,
+) -> eyre::Result<()> {
+    let mut seen_addresses = HashSet::new();
+    if !erase_existing {
+        for account in AccountQuery::get_all_accounts(connection).await? {
+            seen_addresses.insert(account.evm_address.to_lowercase());
+            seen_addresses.insert(account.eclipse_address);
+        }
+    }
+
+    let mut report = ImportReport::default();
+    let mut accepted_rows = vec![];
+
+    for (index, row) in rows.into_iter().enumerate() {
+        let row_number = index + 1;
+
+        let row = match row {
+            Ok(row) => row,
             Err(e) => {
-                if let DbErr::Exec(e) = e {
-                    if e.to_string().contains("UNIQUE constraint failed") {
-                        tracing::warn!(
-                                "EVM private key: {:?} or Eclipse private key: {:?} already exists in the database",
-                                evm_pk,
-                                eclipse_pk,
-                            );
-                    }
-                } else {
-                    tracing::error!("{e}")
-                }
+                report.invalid.push((row_number, e));
+                continue;
             }
+        };
+
+        let evm_address = row.evm.address.to_lowercase();
+        let eclipse_address = row.eclipse.address.clone();
+        let mut details = format!("{} / {}", row.evm.address, row.eclipse.address);
+        if let Some(label) = &row.label {
+            details = format!("{label} | {details}");
         }
+
+        if seen_addresses.contains(&evm_address) || seen_addresses.contains(&eclipse_address) {
+            report
+                .skipped
+                .push((row_number, format!("{details} is already in the database or the files")));
+            continue;
+        }
+
+        seen_addresses.insert(evm_address);
+        seen_addresses.insert(eclipse_address);
+
+        report.accepted.push((row_number, details));
+        accepted_rows.push(row);
     }
 
-    if proxies_iter.len() > 0 {
-        tracing::warn!("There are {} unused proxy entries left in the file.", proxies_iter.len());
+    report.print();
+
+    // nothing is written unless the whole file is valid, so a bad row can't leave the database
+    // erased or half imported
+    if !report.invalid.is_empty() {
+        eyre::bail!(
+            "{} invalid row(s), nothing was imported. Fix them and run the import again",
+            report.invalid.len()
+        );
+    }
+
+    let txn = connection.begin().await?;
+
+    if erase_existing {
+        erase_all_tables(&txn).await?;
+    }
+
+    for row in accepted_rows {
+        let account_id = AccountMutation::create_account(
+            &row.evm.key,
+            &row.eclipse.key,
+            row.proxy,
+            row.label,
+            &txn,
+        )
+        .await?;
+        AccountGoalMutation::create_account_goal(config, account_id, row.goal, &txn).await?;
+        BridgeModuleStateMutation::create_account_bridge_state(account_id, &txn).await?;
+
+        for tag in row.tags.iter().chain(&config.general.tag) {
+            TagMutation::assign_tag(account_id, tag, &txn).await?;
+        }
     }
 
+    txn.commit().await?;
+
     Ok(())
 }
+
+fn validate_key_entries(
+    evm: KeyEntry,
+    eclipse: KeyEntry,
+    proxy: Option<String>,
+) -> Result<ImportRow, String> {
+    let location = format!("EVM line {}, Eclipse line {}", evm.line, eclipse.line);
+
+    let (evm, eclipse) = match (evm.parsed, eclipse.parsed) {
+        (Ok(evm), Ok(eclipse)) => (evm, eclipse),
+        (Err(e), Ok(_)) | (Ok(_), Err(e)) => return Err(e),
+        (Err(evm_error), Err(eclipse_error)) => return Err(format!("{evm_error}; {eclipse_error}")),
+    };
+
+    if let Some(proxy) = &proxy {
+        validate_proxy(proxy).map_err(|e| format!("{location} | Invalid proxy: {e}"))?;
+    }
+
+    Ok(ImportRow { evm, eclipse, proxy, label: None, tags: vec![], goal: GoalOverrides::default() })
+}
+
+fn validate_record(record: AccountRecord, config: &Import) -> Result<ImportRow, String> {
+    let evm = parse_evm_key(record.evm_private_key.trim(), config)
+        .map_err(|e| format!("EVM key | {e}"))?;
+    let eclipse = parse_eclipse_key(record.eclipse_private_key.trim(), config)
+        .map_err(|e| format!("Eclipse key | {e}"))?;
+
+    let non_empty = |value: Option<String>| {
+        value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+    };
+    let proxy = non_empty(record.proxy);
+    let label = non_empty(record.label);
+
+    if let Some(proxy) = &proxy {
+        validate_proxy(proxy).map_err(|e| format!("Invalid proxy: {e}"))?;
+    }
+
+    if record.volume_usd.is_some_and(|volume| !volume.is_finite() || volume < 0.0) {
+        return Err("Invalid volume_usd, expected a positive number".to_string());
+    }
+
+    let targets =
+        [(LifinitySwap.name(), record.swap_count), (UnderdogCreate.name(), record.create_count)]
+            .into_iter()
+            .filter_map(|(action, target)| target.map(|target| (action.to_string(), target)))
+            .collect();
+
+    let goal = GoalOverrides { targets, volume_usd: record.volume_usd };
+
+    let tags = record.tags.as_deref().map(parse_tags).unwrap_or_default();
+
+    Ok(ImportRow { evm, eclipse, proxy, label, tags, goal })
+}
+
+/// Splits a comma separated list of tags, dropping empty entries
+pub fn parse_tags(input: &str) -> Vec<String> {
+    input.split(',').map(str::trim).filter(|tag| !tag.is_empty()).map(str::to_string).collect()
+}
+
+pub fn validate_proxy(proxy: &str) -> Result<(), String> {
+    let url = Url::parse(proxy).map_err(|e| e.to_string())?;
+
+    if !PROXY_SCHEMES.contains(&url.scheme()) {
+        return Err(format!("unsupported scheme `{}`", url.scheme()));
+    }
+
+    if url.host_str().is_none() || url.port_or_known_default().is_none() {
+        return Err("expected the `scheme://log:pass@ip:port` format".to_string());
+    }
+
+    Proxy::all(proxy).map(|_| ()).map_err(|e| e.to_string())
+}
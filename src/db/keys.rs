@@ -0,0 +1,210 @@
+use std::{path::Path, str::FromStr};
+
+use alloy::{
+    hex,
+    signers::local::{
+        coins_bip39::{English, Mnemonic},
+        MnemonicBuilder, PrivateKeySigner,
+    },
+};
+use dialoguer::{theme::ColorfulTheme, Password};
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    signature::Keypair,
+    signer::{
+        keypair::{keypair_from_seed_and_derivation_path, read_keypair_file},
+        Signer,
+    },
+};
+
+use crate::{config::Import, utils::secret::Secret};
+
+use super::constants::KEYSTORE_PASSWORD_ENV_VAR;
+
+const INDEX_PLACEHOLDER: &str = "{index}";
+
+pub struct ParsedKey {
+    pub key: Secret,
+    pub address: String,
+}
+
+/// A wallet read from a key file. A mnemonic line yields one entry per derived wallet
+pub struct KeyEntry {
+    /// 1-based line number in the source file
+    pub line: usize,
+    pub parsed: Result<ParsedKey, String>,
+}
+
+/// Reads EVM wallets from a file where every line is a hex private key, a BIP39 mnemonic or a
+/// path to a JSON keystore v3 file. Keys are returned as hex strings
+pub async fn read_evm_keys(path: impl AsRef<Path>, config: &Import) -> eyre::Result<Vec<KeyEntry>> {
+    read_keys(path.as_ref(), config, |line| parse_evm_line(line, config)).await
+}
+
+/// Reads Eclipse wallets from a file where every line is a base58 private key, a JSON byte array,
+/// a path to a Solana CLI keypair file or a BIP39 mnemonic. Keys are returned as base58 strings
+pub async fn read_eclipse_keys(
+    path: impl AsRef<Path>,
+    config: &Import,
+) -> eyre::Result<Vec<KeyEntry>> {
+    read_keys(path.as_ref(), config, |line| parse_eclipse_line(line, config)).await
+}
+
+/// Invalid lines are kept as entries with an error, so the wallets of the other file stay aligned
+async fn read_keys<F>(path: &Path, config: &Import, parse_line: F) -> eyre::Result<Vec<KeyEntry>>
+where
+    F: Fn(&str) -> eyre::Result<Vec<ParsedKey>>,
+{
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| eyre::eyre!("Failed to read `{}`: {e}", path.display()))?;
+
+    let mut entries = vec![];
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let line_number = index + 1;
+
+        match parse_line(line) {
+            Ok(parsed) => entries.extend(
+                parsed.into_iter().map(|key| KeyEntry { line: line_number, parsed: Ok(key) }),
+            ),
+            Err(e) => {
+                let wallets = if is_mnemonic(line) { config.mnemonic_indexes().count() } else { 1 };
+
+                entries.extend((0..wallets).map(|_| KeyEntry {
+                    line: line_number,
+                    parsed: Err(format!("{}:{line_number} | {e}", path.display())),
+                }));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Parses a single EVM wallet, a mnemonic is only accepted when it derives exactly one wallet
+pub fn parse_evm_key(value: &str, config: &Import) -> eyre::Result<ParsedKey> {
+    single_key(parse_evm_line(value, config)?)
+}
+
+/// Parses a single Eclipse wallet, a mnemonic is only accepted when it derives exactly one wallet
+pub fn parse_eclipse_key(value: &str, config: &Import) -> eyre::Result<ParsedKey> {
+    single_key(parse_eclipse_line(value, config)?)
+}
+
+fn single_key(mut keys: Vec<ParsedKey>) -> eyre::Result<ParsedKey> {
+    match keys.len() {
+        1 => Ok(keys.remove(0)),
+        count => eyre::bail!("Expected a single wallet, the mnemonic derives {count}"),
+    }
+}
+
+fn parse_evm_line(line: &str, config: &Import) -> eyre::Result<Vec<ParsedKey>> {
+    let signers = if line.ends_with(".json") {
+        vec![decrypt_keystore(line)?]
+    } else if is_mnemonic(line) {
+        validate_mnemonic(line)?;
+
+        derivation_paths(&config.evm_derivation_path, config)?
+            .into_iter()
+            .map(|path| {
+                MnemonicBuilder::<English>::default()
+                    .phrase(line)
+                    .derivation_path(&path)
+                    .and_then(|builder| builder.build())
+                    .map_err(|e| eyre::eyre!("Failed to derive the key at `{path}`: {e}"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?
+    } else {
+        vec![PrivateKeySigner::from_str(line)
+            .map_err(|e| eyre::eyre!("Invalid EVM private key: {e}"))?]
+    };
+
+    Ok(signers
+        .into_iter()
+        .map(|signer| ParsedKey {
+            key: Secret::new(hex::encode_prefixed(signer.credential().to_bytes())),
+            address: signer.address().to_string(),
+        })
+        .collect())
+}
+
+fn parse_eclipse_line(line: &str, config: &Import) -> eyre::Result<Vec<ParsedKey>> {
+    let keypairs = if line.starts_with('[') {
+        let bytes = serde_json::from_str::<Vec<u8>>(line)
+            .map_err(|e| eyre::eyre!("Invalid JSON byte array: {e}"))?;
+
+        vec![Keypair::try_from(bytes.as_slice())
+            .map_err(|e| eyre::eyre!("Invalid Eclipse keypair bytes: {e}"))?]
+    } else if line.ends_with(".json") {
+        vec![read_keypair_file(line)
+            .map_err(|e| eyre::eyre!("Failed to read keypair file `{line}`: {e}"))?]
+    } else if is_mnemonic(line) {
+        let seed = validate_mnemonic(line)?
+            .to_seed(None)
+            .map_err(|e| eyre::eyre!("Failed to derive the seed: {e}"))?;
+
+        derivation_paths(&config.eclipse_derivation_path, config)?
+            .into_iter()
+            .map(|path| {
+                let path = DerivationPath::from_absolute_path_str(&path)
+                    .map_err(|e| eyre::eyre!("Invalid derivation path `{path}`: {e}"))?;
+
+                keypair_from_seed_and_derivation_path(&seed, Some(path))
+                    .map_err(|e| eyre::eyre!("Failed to derive the keypair: {e}"))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?
+    } else {
+        let bytes = solana_sdk::bs58::decode(line)
+            .into_vec()
+            .map_err(|e| eyre::eyre!("Invalid base58 string: {e}"))?;
+
+        vec![Keypair::try_from(bytes.as_slice())
+            .map_err(|e| eyre::eyre!("Invalid Eclipse private key: {e}"))?]
+    };
+
+    Ok(keypairs
+        .into_iter()
+        .map(|keypair| ParsedKey {
+            key: Secret::new(keypair.to_base58_string()),
+            address: keypair.pubkey().to_string(),
+        })
+        .collect())
+}
+
+fn is_mnemonic(line: &str) -> bool {
+    line.split_whitespace().count() > 1
+}
+
+/// Errors on the phrase are not forwarded as they can contain the words
+fn validate_mnemonic(line: &str) -> eyre::Result<Mnemonic<English>> {
+    Mnemonic::<English>::new_from_phrase(line)
+        .map_err(|_| eyre::eyre!("Invalid mnemonic, check the word count and spelling"))
+}
+
+fn derivation_paths(template: &str, config: &Import) -> eyre::Result<Vec<String>> {
+    let indexes = config.mnemonic_indexes();
+
+    if indexes.start() != indexes.end() && !template.contains(INDEX_PLACEHOLDER) {
+        eyre::bail!("Derivation path `{template}` has no `{INDEX_PLACEHOLDER}` placeholder");
+    }
+
+    Ok(indexes.map(|index| template.replace(INDEX_PLACEHOLDER, &index.to_string())).collect())
+}
+
+fn decrypt_keystore(path: &str) -> eyre::Result<PrivateKeySigner> {
+    let password = match std::env::var(KEYSTORE_PASSWORD_ENV_VAR) {
+        Ok(password) => password,
+        Err(_) => Password::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Password for `{path}`"))
+            .interact()?,
+    };
+
+    PrivateKeySigner::decrypt_keystore(path, password)
+        .map_err(|e| eyre::eyre!("Failed to decrypt keystore `{path}`: {e}"))
+}
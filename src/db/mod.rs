@@ -8,7 +8,9 @@ mod constants;
 pub mod entities;
 pub mod erase;
 pub mod generate;
+mod keys;
 pub mod service;
+pub mod vault;
 
 pub async fn establish_connection() -> Result<DbConn, DbErr> {
     let database_url = std::env::var("DATABASE_URL").unwrap_or(DEFAULT_DB_URL.to_string());
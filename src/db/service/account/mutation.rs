@@ -1,10 +1,16 @@
 use std::str::FromStr;
 
-use crate::db::{entities::prelude::*, service::prelude::*};
+use crate::{
+    db::{entities::prelude::*, service::prelude::*},
+    utils::{
+        crypto::{cipher, is_encrypted, KeyCipher},
+        secret::Secret,
+    },
+};
 use alloy::signers::local::PrivateKeySigner;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::NotSet, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel,
-    Set,
+    prelude::DateTimeUtc, ActiveModelTrait, ActiveValue::NotSet, ConnectionTrait, DbErr,
+    EntityTrait, IntoActiveModel, Set,
 };
 use solana_sdk::{signature::Keypair, signer::Signer};
 
@@ -12,32 +18,40 @@ pub struct Mutation;
 
 impl Mutation {
     pub async fn create_account(
-        evm_private_key: &str,
-        eclipse_private_key: &str,
+        evm_private_key: &Secret,
+        eclipse_private_key: &Secret,
         proxy: Option<String>,
+        label: Option<String>,
         connection: &impl ConnectionTrait,
     ) -> Result<i32, DbErr> {
-        let evm_pk = PrivateKeySigner::from_str(evm_private_key).map_err(|e| {
+        let evm_pk = PrivateKeySigner::from_str(evm_private_key.expose()).map_err(|e| {
             DbErr::Custom(format!("Invalid EVM private key `{evm_private_key}`: {e}"))
         })?;
 
-        let pk_bytes = &solana_sdk::bs58::decode(&eclipse_private_key).into_vec().map_err(|e| {
-            DbErr::Custom(format!("Invalid base58 string `{eclipse_private_key}`: {e}"))
-        })?;
-        let eclipse_pk = Keypair::from_bytes(pk_bytes).map_err(|e| {
-            DbErr::Custom(format!("Invalid Eclipse private key {eclipse_private_key}: {e}"))
+        let pk_bytes =
+            &solana_sdk::bs58::decode(eclipse_private_key.expose()).into_vec().map_err(|e| {
+                DbErr::Custom(format!("Invalid base58 string `{eclipse_private_key}`: {e}"))
+            })?;
+        let eclipse_pk = Keypair::try_from(pk_bytes.as_slice()).map_err(|e| {
+            DbErr::Custom(format!("Invalid Eclipse private key `{eclipse_private_key}`: {e}"))
         })?;
 
+        let cipher = cipher().map_err(|e| DbErr::Custom(e.to_string()))?;
+        let encrypt = |key: &Secret| {
+            cipher.encrypt(key.expose()).map(Secret::new).map_err(|e| DbErr::Custom(e.to_string()))
+        };
+
         let account = AccountActiveModel {
             id: NotSet,
             is_active: Set(true),
-            evm_private_key: Set(evm_private_key.to_string()),
+            evm_private_key: Set(encrypt(evm_private_key)?),
             evm_address: Set(evm_pk.address().to_string()),
-            eclipse_private_key: Set(eclipse_private_key.to_string()),
+            eclipse_private_key: Set(encrypt(eclipse_private_key)?),
             eclipse_address: Set(eclipse_pk.pubkey().to_string()),
             proxy: Set(proxy),
-            swap_count: Set(0),
-            create_count: Set(0),
+            label: Set(label),
+            swap_volume_usd: Set(0.0),
+            next_action_at: Set(None),
         };
 
         let res = Account::insert(account).exec(connection).await?;
@@ -45,28 +59,80 @@ impl Mutation {
         Ok(res.last_insert_id)
     }
 
-    pub async fn increase_swap_count(
+    /// Replaces plaintext keys of an account created before encryption was introduced
+    pub async fn encrypt_private_keys(
+        acc: AccountModel,
+        cipher: &KeyCipher,
+        connection: &impl ConnectionTrait,
+    ) -> Result<AccountModel, DbErr> {
+        let encrypt = |key: &Secret| match is_encrypted(key.expose()) {
+            true => Ok(key.clone()),
+            false => cipher
+                .encrypt(key.expose())
+                .map(Secret::new)
+                .map_err(|e| DbErr::Custom(e.to_string())),
+        };
+
+        let evm_private_key = Set(encrypt(&acc.evm_private_key)?);
+        let eclipse_private_key = Set(encrypt(&acc.eclipse_private_key)?);
+
+        let mut account = acc.into_active_model();
+        account.evm_private_key = evm_private_key;
+        account.eclipse_private_key = eclipse_private_key;
+
+        account.update(connection).await
+    }
+
+    pub async fn add_swap_volume(
         account_id: i32,
+        volume_usd: f64,
         connection: &impl ConnectionTrait,
     ) -> Result<AccountModel, DbErr> {
         let acc = AccountQuery::find_account_by_id(account_id, connection).await?;
-        let new_swap_count = Set(acc.swap_count + 1);
+        let new_swap_volume_usd = Set(acc.swap_volume_usd + volume_usd);
 
         let mut account = acc.into_active_model();
-        account.swap_count = new_swap_count;
+        account.swap_volume_usd = new_swap_volume_usd;
 
         account.update(connection).await
     }
 
-    pub async fn increase_create_count(
+    pub async fn set_active(
         account_id: i32,
+        is_active: bool,
+        connection: &impl ConnectionTrait,
+    ) -> Result<AccountModel, DbErr> {
+        let acc = AccountQuery::find_account_by_id(account_id, connection).await?;
+
+        let mut account = acc.into_active_model();
+        account.is_active = Set(is_active);
+
+        account.update(connection).await
+    }
+
+    /// Workers skip the account until `next_action_at`, `None` makes it due right away
+    pub async fn set_next_action_at(
+        account_id: i32,
+        next_action_at: Option<DateTimeUtc>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<AccountModel, DbErr> {
+        let acc = AccountQuery::find_account_by_id(account_id, connection).await?;
+
+        let mut account = acc.into_active_model();
+        account.next_action_at = Set(next_action_at);
+
+        account.update(connection).await
+    }
+
+    pub async fn set_proxy(
+        account_id: i32,
+        proxy: Option<String>,
         connection: &impl ConnectionTrait,
     ) -> Result<AccountModel, DbErr> {
         let acc = AccountQuery::find_account_by_id(account_id, connection).await?;
-        let new_create_count = Set(acc.create_count + 1);
 
         let mut account = acc.into_active_model();
-        account.create_count = new_create_count;
+        account.proxy = Set(proxy);
 
         account.update(connection).await
     }
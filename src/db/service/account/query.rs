@@ -1,6 +1,11 @@
-use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
 
-use crate::db::entities::{account, prelude::*};
+use crate::db::{
+    entities::{account, prelude::*},
+    service::prelude::TagQuery,
+};
 
 pub struct Query;
 
@@ -18,33 +23,55 @@ impl Query {
         Err(DbErr::RecordNotFound(format!("Account with id: {account_id} not found")))
     }
 
-    pub async fn get_active_accounts(
+    pub async fn get_all_accounts(
         connection: &impl ConnectionTrait,
     ) -> Result<Vec<AccountModel>, DbErr> {
-        let account_vec =
-            Account::find().filter(account::Column::IsActive.eq(true)).all(connection).await?;
+        Self::get_accounts(None, connection).await
+    }
 
-        match account_vec.is_empty() {
-            true => Err(DbErr::RecordNotFound("No active accounts found".to_string())),
-            false => Ok(account_vec),
-        }
+    /// Accounts whose EVM address, Eclipse address or label contain `query`
+    pub async fn search_accounts(
+        query: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<AccountModel>, DbErr> {
+        let pattern = format!("%{query}%");
+
+        Account::find()
+            .filter(
+                Condition::any()
+                    .add(account::Column::EvmAddress.like(&pattern))
+                    .add(account::Column::EclipseAddress.like(&pattern))
+                    .add(account::Column::Label.like(&pattern)),
+            )
+            .order_by_asc(account::Column::Id)
+            .all(connection)
+            .await
     }
 
-    pub async fn get_active_accounts_ids_by_ids(
-        ids: &[i32],
+    pub async fn get_accounts(
+        tag: Option<&str>,
         connection: &impl ConnectionTrait,
-    ) -> Result<Vec<i32>, DbErr> {
-        let accounts = Account::find()
-            .filter(account::Column::IsActive.eq(true).and(account::Column::Id.is_in(ids.to_vec())))
+    ) -> Result<Vec<AccountModel>, DbErr> {
+        Account::find()
+            .filter(TagQuery::tag_filter(account::Column::Id, tag))
+            .order_by_asc(account::Column::Id)
+            .all(connection)
+            .await
+    }
+
+    pub async fn get_active_accounts(
+        tag: Option<&str>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<AccountModel>, DbErr> {
+        let account_vec = Account::find()
+            .filter(account::Column::IsActive.eq(true))
+            .filter(TagQuery::tag_filter(account::Column::Id, tag))
             .all(connection)
             .await?;
 
-        if accounts.is_empty() {
-            Err(DbErr::RecordNotFound(
-                "No active accounts IDs found for the provided IDs".to_string(),
-            ))
-        } else {
-            Ok(accounts.into_iter().map(|state| state.id).collect())
+        match account_vec.is_empty() {
+            true => Err(DbErr::RecordNotFound("No active accounts found".to_string())),
+            false => Ok(account_vec),
         }
     }
 }
@@ -1,26 +1,72 @@
-use crate::{config::Config, db::entities::prelude::*, utils::misc::random_in_range};
+use std::collections::HashMap;
 
-use sea_orm::{ConnectionTrait, DbErr, EntityTrait, Set};
+use crate::{
+    config::Config,
+    db::{
+        entities::prelude::*,
+        service::prelude::{AccountGoalQuery, ActionProgressMutation},
+    },
+    modules::warmup::action::ACTIONS,
+    utils::misc::random_in_range,
+};
+
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, Set};
+
+/// Goal values set explicitly for an account, the missing ones are rolled from the config ranges
+#[derive(Clone, Debug, Default)]
+pub struct GoalOverrides {
+    /// Targets keyed by the action name
+    pub targets: HashMap<String, u32>,
+    pub volume_usd: Option<f64>,
+}
 
 pub struct Mutation;
 
 impl Mutation {
+    /// Creates the goal of the account along with a progress row for every registered action
     pub async fn create_account_goal(
         config: &Config,
         account_id: i32,
+        overrides: GoalOverrides,
         connection: &impl ConnectionTrait,
     ) -> Result<i32, DbErr> {
-        let swap_count = random_in_range(config.lifinity.swaps_count_range);
-        let create_count = random_in_range(config.underdog.create_count_range);
+        let volume_usd =
+            overrides.volume_usd.or_else(|| config.lifinity.volume_usd_range.map(random_in_range));
 
-        let account_goal = AccountGoalActiveModel {
-            id: Set(account_id),
-            swap_count: Set(swap_count as i32),
-            create_count: Set(create_count as i32),
-        };
+        let account_goal =
+            AccountGoalActiveModel { id: Set(account_id), volume_usd: Set(volume_usd) };
 
         let res = AccountGoal::insert(account_goal).exec(connection).await?;
 
+        for action in ACTIONS {
+            let target = overrides
+                .targets
+                .get(action.name())
+                .copied()
+                .unwrap_or_else(|| random_in_range(action.target_range(config)));
+
+            ActionProgressMutation::set_target(
+                account_id,
+                action.name(),
+                target as i32,
+                connection,
+            )
+            .await?;
+        }
+
         Ok(res.last_insert_id)
     }
+
+    pub async fn update_account_goal(
+        account_id: i32,
+        volume_usd: Option<f64>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<AccountGoalModel, DbErr> {
+        let goal = AccountGoalQuery::get_account_goal_by_id(account_id, connection).await?;
+
+        let mut goal = goal.into_active_model();
+        goal.volume_usd = Set(volume_usd);
+
+        goal.update(connection).await
+    }
 }
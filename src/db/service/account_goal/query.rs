@@ -9,12 +9,18 @@ impl Query {
         account_id: i32,
         connection: &impl ConnectionTrait,
     ) -> Result<AccountGoalModel, DbErr> {
-        let goal = AccountGoal::find()
+        AccountGoal::find()
             .filter(account_goal::Column::Id.eq(account_id))
             .one(connection)
             .await?
-            .unwrap();
+            .ok_or_else(|| {
+                DbErr::RecordNotFound(format!("Goal of account with id: {account_id} not found"))
+            })
+    }
 
-        Ok(goal)
+    pub async fn get_all_account_goals(
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<AccountGoalModel>, DbErr> {
+        AccountGoal::find().all(connection).await
     }
 }
@@ -0,0 +1,75 @@
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ConnectionTrait, DbErr, EntityTrait, Set,
+};
+
+use crate::db::{
+    entities::{action_progress, prelude::*},
+    service::prelude::ActionProgressQuery,
+};
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Sets the goal of an action, the progress row is created when the account has none yet
+    pub async fn set_target(
+        account_id: i32,
+        action_name: &str,
+        target: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<ActionProgressModel, DbErr> {
+        let progress = ActionProgressActiveModel {
+            account_id: Set(account_id),
+            action_name: Set(action_name.to_string()),
+            target: Set(target),
+            done: Set(0),
+        };
+
+        ActionProgress::insert(progress)
+            .on_conflict(
+                OnConflict::columns([
+                    action_progress::Column::AccountId,
+                    action_progress::Column::ActionName,
+                ])
+                .update_column(action_progress::Column::Target)
+                .to_owned(),
+            )
+            .exec_without_returning(connection)
+            .await?;
+
+        ActionProgressQuery::get_action_progress(account_id, action_name, connection).await
+    }
+
+    /// Counts a finished run of the action. The row is created with no target when the account has
+    /// none, e.g. for an action added after the account was, so a run that already landed on-chain
+    /// is always recorded
+    pub async fn increase_done(
+        account_id: i32,
+        action_name: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<ActionProgressModel, DbErr> {
+        let progress = ActionProgressActiveModel {
+            account_id: Set(account_id),
+            action_name: Set(action_name.to_string()),
+            target: Set(0),
+            done: Set(1),
+        };
+
+        ActionProgress::insert(progress)
+            .on_conflict(
+                OnConflict::columns([
+                    action_progress::Column::AccountId,
+                    action_progress::Column::ActionName,
+                ])
+                .value(
+                    action_progress::Column::Done,
+                    Expr::col((ActionProgress, action_progress::Column::Done)).add(1),
+                )
+                .to_owned(),
+            )
+            .exec_without_returning(connection)
+            .await?;
+
+        ActionProgressQuery::get_action_progress(account_id, action_name, connection).await
+    }
+}
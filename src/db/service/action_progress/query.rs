@@ -0,0 +1,40 @@
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+use crate::db::entities::{action_progress, prelude::*};
+
+pub struct Query;
+
+impl Query {
+    pub async fn get_action_progress(
+        account_id: i32,
+        action_name: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<ActionProgressModel, DbErr> {
+        ActionProgress::find()
+            .filter(action_progress::Column::AccountId.eq(account_id))
+            .filter(action_progress::Column::ActionName.eq(action_name))
+            .one(connection)
+            .await?
+            .ok_or_else(|| {
+                DbErr::RecordNotFound(format!(
+                    "Progress of `{action_name}` for account with id: {account_id} not found"
+                ))
+            })
+    }
+
+    pub async fn get_account_progress(
+        account_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<ActionProgressModel>, DbErr> {
+        ActionProgress::find()
+            .filter(action_progress::Column::AccountId.eq(account_id))
+            .all(connection)
+            .await
+    }
+
+    pub async fn get_all_progress(
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<ActionProgressModel>, DbErr> {
+        ActionProgress::find().all(connection).await
+    }
+}
@@ -1,4 +1,5 @@
-use crate::db::entities::prelude::*;
+use crate::db::entities::{prelude::*, sea_orm_active_enums::BridgeStatus};
+use chrono::Utc;
 use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, Set};
 
 pub struct Mutation;
@@ -6,18 +7,154 @@ pub struct Mutation;
 use super::query::Query;
 
 impl Mutation {
-    pub async fn set_funds_bridged(
+    /// Records a signed deposit along with the Eclipse balance it is expected to add to. Written
+    /// before the broadcast, so a deposit is never sent without the next run knowing about it. The
+    /// hash is kept next to the ones signed before it for the same nonce
+    pub async fn set_submitted(
         account_id: i32,
+        tx_hash: impl ToString,
+        nonce: u64,
+        amount: impl ToString,
+        eclipse_balance_before: u64,
+        connection: &impl ConnectionTrait,
+    ) -> Result<BridgeStateModel, DbErr> {
+        if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
+            let deposit_tx = BridgeDepositTxActiveModel {
+                tx_hash: Set(tx_hash.to_string()),
+                account_id: Set(account_id),
+                nonce: Set(nonce as i64),
+                created_at: Set(Utc::now()),
+            };
+            BridgeDepositTx::insert(deposit_tx).exec_without_returning(connection).await?;
+
+            let mut state = state.into_active_model();
+            state.status = Set(Some(BridgeStatus::Submitted));
+            state.tx_hash = Set(Some(tx_hash.to_string()));
+            state.nonce = Set(Some(nonce as i64));
+            state.amount = Set(Some(amount.to_string()));
+            state.eclipse_balance_before = Set(Some(eclipse_balance_before as i64));
+            state.submitted_at = Set(Some(Utc::now()));
+            state.confirmed_at = Set(None);
+            state.arrived_at = Set(None);
+
+            state.update(connection).await
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Failed to record the deposit for account with id {account_id}"
+            )))
+        }
+    }
+
+    /// Moves the deposit to `status` and stamps the time it got there. The account counts as
+    /// bridged once the deposit is confirmed on mainnet, `None` marks a reverted deposit, which
+    /// used up its nonce
+    pub async fn set_deposit_status(
+        account_id: i32,
+        status: Option<BridgeStatus>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<BridgeStateModel, DbErr> {
+        if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
+            let mut state = state.into_active_model();
+            state.status = Set(status);
+
+            match status {
+                Some(BridgeStatus::ConfirmedL1) => {
+                    state.funds_bridged = Set(true);
+                    state.skip_reason = Set(None);
+                    state.confirmed_at = Set(Some(Utc::now()));
+                }
+                Some(BridgeStatus::ArrivedL2) => {
+                    state.funds_bridged = Set(true);
+                    state.skip_reason = Set(None);
+                    state.arrived_at = Set(Some(Utc::now()));
+                }
+                Some(BridgeStatus::Submitted | BridgeStatus::Stuck | BridgeStatus::NeedsReview) => {
+                }
+                None => {
+                    state.funds_bridged = Set(false);
+                    state.nonce = Set(None);
+                }
+            }
+
+            state.update(connection).await
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Failed to set the deposit status for account with id {account_id}"
+            )))
+        }
+    }
+
+    /// Clears a deposit the network doesn't know about. Its nonce is kept, the next deposit has to
+    /// reuse it so that at most one of the two can be mined
+    pub async fn set_dropped(
+        account_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<BridgeStateModel, DbErr> {
+        if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
+            let mut state = state.into_active_model();
+            state.status = Set(None);
+            state.funds_bridged = Set(false);
+
+            state.update(connection).await
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Failed to clear the deposit for account with id {account_id}"
+            )))
+        }
+    }
+
+    /// Records the hash of the deposit that got mined, it may be another one than the last signed
+    pub async fn set_tx_hash(
+        account_id: i32,
+        tx_hash: impl ToString,
+        connection: &impl ConnectionTrait,
+    ) -> Result<BridgeStateModel, DbErr> {
+        if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
+            let mut state = state.into_active_model();
+            state.tx_hash = Set(Some(tx_hash.to_string()));
+
+            state.update(connection).await
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Failed to set the deposit hash for account with id {account_id}"
+            )))
+        }
+    }
+
+    /// Parks a deposit that may have been mined without any of its known hashes. The account isn't
+    /// bridged again until the status is changed by hand
+    pub async fn set_needs_review(
+        account_id: i32,
+        reason: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<BridgeStateModel, DbErr> {
+        if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
+            let mut state = state.into_active_model();
+            state.status = Set(Some(BridgeStatus::NeedsReview));
+            state.skip_reason = Set(Some(reason.to_string()));
+
+            state.update(connection).await
+        } else {
+            Err(DbErr::RecordNotFound(format!(
+                "Failed to flag the deposit for account with id {account_id}"
+            )))
+        }
+    }
+
+    /// Records why the account was left unbridged, it is tried again on the next run
+    pub async fn set_skip_reason(
+        account_id: i32,
+        reason: &str,
         connection: &impl ConnectionTrait,
     ) -> Result<BridgeStateModel, DbErr> {
         if let Some(state) = Query::get_account_bridge_state_by_id(account_id, connection).await? {
             let mut state = state.into_active_model();
-            state.funds_bridged = Set(true);
+            state.skip_reason = Set(Some(reason.to_string()));
 
             state.update(connection).await
         } else {
             Err(DbErr::RecordNotFound(format!(
-                "Failed to set funds bridge for account with id {account_id}"
+                "Failed to set the skip reason for account with id {account_id}"
             )))
         }
     }
@@ -26,8 +163,19 @@ impl Mutation {
         account_id: i32,
         connection: &impl ConnectionTrait,
     ) -> Result<i32, DbErr> {
-        let account_bridge_state =
-            BridgeStateActiveModel { id: Set(account_id), funds_bridged: Set(false) };
+        let account_bridge_state = BridgeStateActiveModel {
+            id: Set(account_id),
+            funds_bridged: Set(false),
+            skip_reason: Set(None),
+            status: Set(None),
+            tx_hash: Set(None),
+            nonce: Set(None),
+            amount: Set(None),
+            eclipse_balance_before: Set(None),
+            submitted_at: Set(None),
+            confirmed_at: Set(None),
+            arrived_at: Set(None),
+        };
 
         let res = BridgeModuleState::insert(account_bridge_state).exec(connection).await?;
 
@@ -1,18 +1,34 @@
-use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
 
-use crate::db::entities::{
-    bridge_module_state,
-    prelude::{BridgeModuleState, BridgeStateModel},
+use crate::db::{
+    entities::{
+        bridge_deposit_tx, bridge_module_state,
+        prelude::{BridgeDepositTx, BridgeModuleState, BridgeStateModel},
+        sea_orm_active_enums::BridgeStatus,
+    },
+    service::prelude::TagQuery,
 };
 
 pub struct Query;
 
 impl Query {
     pub async fn get_accounts_with_unbridged_state(
+        tag: Option<&str>,
         connection: &impl ConnectionTrait,
     ) -> Result<Vec<BridgeStateModel>, DbErr> {
         let state_vec = BridgeModuleState::find()
             .filter(bridge_module_state::Column::FundsBridged.eq(false))
+            // a submitted deposit may still be mined, it has to be reconciled or reviewed first
+            .filter(
+                Condition::any().add(bridge_module_state::Column::Status.is_null()).add(
+                    bridge_module_state::Column::Status
+                        .is_not_in([BridgeStatus::Submitted, BridgeStatus::NeedsReview]),
+                ),
+            )
+            .filter(TagQuery::tag_filter(bridge_module_state::Column::Id, tag))
             .all(connection)
             .await?;
 
@@ -24,28 +40,6 @@ impl Query {
         }
     }
 
-    pub async fn get_unbridged_state_ids_by_ids(
-        ids: &[i32],
-        connection: &impl ConnectionTrait,
-    ) -> Result<Vec<i32>, DbErr> {
-        let states = BridgeModuleState::find()
-            .filter(
-                bridge_module_state::Column::FundsBridged
-                    .eq(false)
-                    .and(bridge_module_state::Column::Id.is_in(ids.to_vec())),
-            )
-            .all(connection)
-            .await?;
-
-        if states.is_empty() {
-            Err(DbErr::RecordNotFound(
-                "No unbridged state IDs found for the provided IDs".to_string(),
-            ))
-        } else {
-            Ok(states.into_iter().map(|state| state.id).collect())
-        }
-    }
-
     pub async fn get_account_bridge_state_by_id(
         account_id: i32,
         connection: &impl ConnectionTrait,
@@ -55,4 +49,51 @@ impl Query {
             .one(connection)
             .await
     }
+
+    /// Deposits of the accounts matching `tag` that are in one of `statuses`
+    pub async fn get_deposits(
+        tag: Option<&str>,
+        statuses: &[BridgeStatus],
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<BridgeStateModel>, DbErr> {
+        BridgeModuleState::find()
+            .filter(bridge_module_state::Column::Status.is_in(statuses.iter().copied()))
+            .filter(TagQuery::tag_filter(bridge_module_state::Column::Id, tag))
+            .all(connection)
+            .await
+    }
+
+    /// Hashes of every deposit the account signed with `nonce`, oldest first
+    pub async fn get_deposit_tx_hashes(
+        account_id: i32,
+        nonce: i64,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<String>, DbErr> {
+        BridgeDepositTx::find()
+            .select_only()
+            .column(bridge_deposit_tx::Column::TxHash)
+            .filter(bridge_deposit_tx::Column::AccountId.eq(account_id))
+            .filter(bridge_deposit_tx::Column::Nonce.eq(nonce))
+            .order_by_asc(bridge_deposit_tx::Column::CreatedAt)
+            .into_tuple()
+            .all(connection)
+            .await
+    }
+
+    /// Ids of the accounts whose deposit showed up on Eclipse
+    pub async fn get_arrived_ids(connection: &impl ConnectionTrait) -> Result<Vec<i32>, DbErr> {
+        BridgeModuleState::find()
+            .select_only()
+            .column(bridge_module_state::Column::Id)
+            .filter(bridge_module_state::Column::Status.eq(BridgeStatus::ArrivedL2))
+            .into_tuple()
+            .all(connection)
+            .await
+    }
+
+    pub async fn get_all_bridge_states(
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<BridgeStateModel>, DbErr> {
+        BridgeModuleState::find().all(connection).await
+    }
 }
@@ -1,4 +1,10 @@
 pub mod account;
 pub mod account_goal;
+pub mod action_progress;
 pub mod bridge_module_state;
+pub mod plan;
 pub mod prelude;
+pub mod scenario_progress;
+pub mod tag;
+pub mod tx_history;
+pub mod vault;
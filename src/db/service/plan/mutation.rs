@@ -0,0 +1,97 @@
+use chrono::Utc;
+use sea_orm::{
+    prelude::DateTimeUtc, ActiveModelTrait, ActiveValue::NotSet, ConnectionTrait, DbErr,
+    EntityTrait, IntoActiveModel, Set,
+};
+
+use crate::db::entities::prelude::*;
+
+/// Keeps every insert below the SQLite limit of bound parameters
+const INSERT_CHUNK_SIZE: usize = 500;
+
+pub struct Mutation;
+
+/// A single action of a new plan, `params` is the JSON encoded table passed to the action
+pub struct PlannedStep {
+    pub account_id: i32,
+    pub step: i32,
+    pub action_name: String,
+    pub params: String,
+    pub scheduled_at: DateTimeUtc,
+}
+
+impl Mutation {
+    /// Stores a new unapproved plan, replacing the previous one along with its actions
+    /// `scenario` is the name and the number of steps of the scenario the plan was rolled from
+    pub async fn create_plan(
+        steps: Vec<PlannedStep>,
+        scenario: Option<(&str, usize)>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<PlanModel, DbErr> {
+        Self::delete_plans(connection).await?;
+
+        let plan = PlanActiveModel {
+            id: NotSet,
+            created_at: Set(Utc::now()),
+            approved_at: Set(None),
+            scenario: Set(scenario.map(|(name, _)| name.to_string())),
+            scenario_steps: Set(scenario.map(|(_, steps)| steps as i32)),
+        }
+        .insert(connection)
+        .await?;
+
+        let actions = steps.into_iter().map(|step| PlannedActionActiveModel {
+            id: NotSet,
+            plan_id: Set(plan.id),
+            account_id: Set(step.account_id),
+            step: Set(step.step),
+            action_name: Set(step.action_name),
+            params: Set(step.params),
+            scheduled_at: Set(step.scheduled_at),
+            executed_at: Set(None),
+        });
+
+        for chunk in actions.collect::<Vec<_>>().chunks(INSERT_CHUNK_SIZE) {
+            PlannedAction::insert_many(chunk.to_vec()).exec_without_returning(connection).await?;
+        }
+
+        Ok(plan)
+    }
+
+    pub async fn approve_plan(
+        plan: PlanModel,
+        connection: &impl ConnectionTrait,
+    ) -> Result<PlanModel, DbErr> {
+        let mut plan = plan.into_active_model();
+        plan.approved_at = Set(Some(Utc::now()));
+
+        plan.update(connection).await
+    }
+
+    pub async fn delete_plans(connection: &impl ConnectionTrait) -> Result<(), DbErr> {
+        PlannedAction::delete_many().exec(connection).await?;
+        Plan::delete_many().exec(connection).await?;
+
+        Ok(())
+    }
+
+    pub async fn mark_executed(
+        planned_action_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<(), DbErr> {
+        let action = PlannedAction::find_by_id(planned_action_id)
+            .one(connection)
+            .await?
+            .ok_or_else(|| {
+                DbErr::RecordNotFound(format!(
+                    "Planned action with id: {planned_action_id} not found"
+                ))
+            })?;
+
+        let mut action = action.into_active_model();
+        action.executed_at = Set(Some(Utc::now()));
+        action.update(connection).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+
+use crate::db::entities::{plan, planned_action, prelude::*};
+
+pub struct Query;
+
+impl Query {
+    /// Only one plan is kept at a time, generating a new one replaces it
+    pub async fn get_plan(connection: &impl ConnectionTrait) -> Result<Option<PlanModel>, DbErr> {
+        Plan::find().order_by_desc(plan::Column::Id).one(connection).await
+    }
+
+    pub async fn get_planned_actions(
+        plan_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<PlannedActionModel>, DbErr> {
+        PlannedAction::find()
+            .filter(planned_action::Column::PlanId.eq(plan_id))
+            .order_by_asc(planned_action::Column::AccountId)
+            .order_by_asc(planned_action::Column::Step)
+            .all(connection)
+            .await
+    }
+
+    /// First action of the account that was not executed yet
+    pub async fn get_next_planned_action(
+        plan_id: i32,
+        account_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Option<PlannedActionModel>, DbErr> {
+        PlannedAction::find()
+            .filter(planned_action::Column::PlanId.eq(plan_id))
+            .filter(planned_action::Column::AccountId.eq(account_id))
+            .filter(planned_action::Column::ExecutedAt.is_null())
+            .order_by_asc(planned_action::Column::Step)
+            .one(connection)
+            .await
+    }
+
+    /// Ids of the accounts with actions left in the plan
+    pub async fn get_pending_account_ids(
+        plan_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<i32>, DbErr> {
+        PlannedAction::find()
+            .select_only()
+            .column(planned_action::Column::AccountId)
+            .distinct()
+            .filter(planned_action::Column::PlanId.eq(plan_id))
+            .filter(planned_action::Column::ExecutedAt.is_null())
+            .into_tuple::<i32>()
+            .all(connection)
+            .await
+    }
+}
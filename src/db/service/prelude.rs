@@ -1,7 +1,26 @@
 pub use super::{
     account::{mutation::Mutation as AccountMutation, query::Query as AccountQuery},
-    account_goal::{mutation::Mutation as AccountGoalMutation, query::Query as AccountGoalQuery},
+    account_goal::{
+        mutation::{GoalOverrides, Mutation as AccountGoalMutation},
+        query::Query as AccountGoalQuery,
+    },
+    action_progress::{
+        mutation::Mutation as ActionProgressMutation, query::Query as ActionProgressQuery,
+    },
     bridge_module_state::{
         mutation::Mutation as BridgeModuleStateMutation, query::Query as BridgeModuleStateQuery,
     },
+    plan::{
+        mutation::{Mutation as PlanMutation, PlannedStep},
+        query::Query as PlanQuery,
+    },
+    scenario_progress::{
+        mutation::Mutation as ScenarioProgressMutation, query::Query as ScenarioProgressQuery,
+    },
+    tag::{mutation::Mutation as TagMutation, query::Query as TagQuery},
+    tx_history::{
+        mutation::{Mutation as TxHistoryMutation, TxRecord},
+        query::Query as TxHistoryQuery,
+    },
+    vault::{mutation::Mutation as VaultMutation, query::Query as VaultQuery},
 };
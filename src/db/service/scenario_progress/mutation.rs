@@ -0,0 +1,39 @@
+use sea_orm::{sea_query::OnConflict, ConnectionTrait, DbErr, EntityTrait, Set};
+
+use crate::db::entities::{prelude::*, scenario_progress};
+
+pub struct Mutation;
+
+impl Mutation {
+    /// Stores the position of the account in the scenario. `runs_left` is `None` until the repeat
+    /// count of the step is rolled
+    pub async fn set_position(
+        account_id: i32,
+        scenario: &str,
+        step: usize,
+        runs_left: Option<u32>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<(), DbErr> {
+        let progress = ScenarioProgressActiveModel {
+            account_id: Set(account_id),
+            scenario: Set(scenario.to_string()),
+            step: Set(step as i32),
+            runs_left: Set(runs_left.map(|runs| runs as i32)),
+        };
+
+        ScenarioProgress::insert(progress)
+            .on_conflict(
+                OnConflict::column(scenario_progress::Column::AccountId)
+                    .update_columns([
+                        scenario_progress::Column::Scenario,
+                        scenario_progress::Column::Step,
+                        scenario_progress::Column::RunsLeft,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(connection)
+            .await?;
+
+        Ok(())
+    }
+}
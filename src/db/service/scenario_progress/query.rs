@@ -0,0 +1,14 @@
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+
+use crate::db::entities::prelude::*;
+
+pub struct Query;
+
+impl Query {
+    pub async fn get_scenario_progress(
+        account_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Option<ScenarioProgressModel>, DbErr> {
+        ScenarioProgress::find_by_id(account_id).one(connection).await
+    }
+}
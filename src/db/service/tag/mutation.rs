@@ -0,0 +1,67 @@
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    QueryFilter, Set,
+};
+
+use crate::db::{
+    entities::{account_tag, prelude::*},
+    service::prelude::TagQuery,
+};
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn get_or_create_tag(
+        name: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<i32, DbErr> {
+        if let Some(tag) = TagQuery::find_tag_by_name(name, connection).await? {
+            return Ok(tag.id);
+        }
+
+        let tag = TagActiveModel { id: NotSet, name: Set(name.to_string()) };
+        let res = Tag::insert(tag).exec(connection).await?;
+
+        Ok(res.last_insert_id)
+    }
+
+    pub async fn assign_tag(
+        account_id: i32,
+        name: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<(), DbErr> {
+        let tag_id = Self::get_or_create_tag(name, connection).await?;
+        let account_tag =
+            AccountTagActiveModel { account_id: Set(account_id), tag_id: Set(tag_id) };
+
+        AccountTag::insert(account_tag)
+            .on_conflict(
+                OnConflict::columns([account_tag::Column::AccountId, account_tag::Column::TagId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .do_nothing()
+            .exec(connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces every tag of the account with `names`
+    pub async fn set_account_tags(
+        account_id: i32,
+        names: &[String],
+        connection: &impl ConnectionTrait,
+    ) -> Result<(), DbErr> {
+        AccountTag::delete_many()
+            .filter(account_tag::Column::AccountId.eq(account_id))
+            .exec(connection)
+            .await?;
+
+        for name in names {
+            Self::assign_tag(account_id, name, connection).await?;
+        }
+
+        Ok(())
+    }
+}
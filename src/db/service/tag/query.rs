@@ -0,0 +1,50 @@
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait,
+};
+
+use crate::db::entities::{account_tag, prelude::*, tag};
+
+pub struct Query;
+
+impl Query {
+    pub async fn get_all_tags(connection: &impl ConnectionTrait) -> Result<Vec<TagModel>, DbErr> {
+        Tag::find().order_by_asc(tag::Column::Name).all(connection).await
+    }
+
+    pub async fn find_tag_by_name(
+        name: &str,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Option<TagModel>, DbErr> {
+        Tag::find().filter(tag::Column::Name.eq(name)).one(connection).await
+    }
+
+    pub async fn get_account_tags(
+        account_id: i32,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<TagModel>, DbErr> {
+        Tag::find()
+            .inner_join(AccountTag)
+            .filter(account_tag::Column::AccountId.eq(account_id))
+            .order_by_asc(tag::Column::Name)
+            .all(connection)
+            .await
+    }
+
+    /// Condition that keeps the rows whose `account_id_column` points to an account with the tag.
+    /// Without a tag every row is kept
+    pub fn tag_filter(account_id_column: impl ColumnTrait, tag: Option<&str>) -> Condition {
+        let Some(tag) = tag else {
+            return Condition::all();
+        };
+
+        let tagged_account_ids = AccountTag::find()
+            .select_only()
+            .column(account_tag::Column::AccountId)
+            .inner_join(Tag)
+            .filter(tag::Column::Name.eq(tag))
+            .into_query();
+
+        Condition::all().add(account_id_column.in_subquery(tagged_account_ids))
+    }
+}
@@ -0,0 +1,99 @@
+use crate::db::entities::{
+    prelude::*,
+    sea_orm_active_enums::{Chain, TxAction, TxStatus},
+};
+use chrono::Utc;
+use sea_orm::{
+    prelude::DateTimeUtc, ActiveValue::NotSet, ConnectionTrait, DbErr, EntityTrait, Set,
+};
+
+pub struct Mutation;
+
+/// A single on-chain action, `created_at` is the moment the action was started
+pub struct TxRecord {
+    account_id: i32,
+    chain: Chain,
+    action: TxAction,
+    tx_hash: Option<String>,
+    token_in: Option<String>,
+    token_out: Option<String>,
+    amount_in: Option<String>,
+    amount_out: Option<String>,
+    fee: Option<String>,
+    error: Option<String>,
+    created_at: DateTimeUtc,
+}
+
+impl TxRecord {
+    pub fn new(account_id: i32, chain: Chain, action: TxAction) -> Self {
+        Self {
+            account_id,
+            chain,
+            action,
+            tx_hash: None,
+            token_in: None,
+            token_out: None,
+            amount_in: None,
+            amount_out: None,
+            fee: None,
+            error: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn tx_hash(mut self, tx_hash: impl ToString) -> Self {
+        self.tx_hash = Some(tx_hash.to_string());
+        self
+    }
+
+    pub fn token_in(mut self, token: &str, amount: impl ToString) -> Self {
+        self.token_in = Some(token.to_string());
+        self.amount_in = Some(amount.to_string());
+        self
+    }
+
+    pub fn token_out(mut self, token: &str, amount: Option<impl ToString>) -> Self {
+        self.token_out = Some(token.to_string());
+        self.amount_out = amount.map(|amount| amount.to_string());
+        self
+    }
+
+    pub fn fee(mut self, fee: Option<impl ToString>) -> Self {
+        self.fee = fee.map(|fee| fee.to_string());
+        self
+    }
+
+    pub fn error(mut self, error: impl ToString) -> Self {
+        self.error = Some(error.to_string());
+        self
+    }
+}
+
+impl Mutation {
+    pub async fn create_tx_record(
+        record: TxRecord,
+        status: TxStatus,
+        connection: &impl ConnectionTrait,
+    ) -> Result<i32, DbErr> {
+        let tx = TxHistoryActiveModel {
+            id: NotSet,
+            account_id: Set(record.account_id),
+            chain: Set(record.chain),
+            action: Set(record.action),
+            tx_hash: Set(record.tx_hash),
+            token_in: Set(record.token_in),
+            token_out: Set(record.token_out),
+            amount_in: Set(record.amount_in),
+            amount_out: Set(record.amount_out),
+            fee: Set(record.fee),
+            status: Set(status),
+            error: Set(record.error),
+            created_at: Set(record.created_at),
+            updated_at: Set(Utc::now()),
+        };
+
+        let res = TxHistory::insert(tx).exec(connection).await?;
+
+        Ok(res.last_insert_id)
+    }
+}
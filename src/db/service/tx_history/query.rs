@@ -0,0 +1,20 @@
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::db::entities::{prelude::*, tx_history};
+
+pub struct Query;
+
+impl Query {
+    pub async fn get_tx_history(
+        account_id: Option<i32>,
+        connection: &impl ConnectionTrait,
+    ) -> Result<Vec<TxHistoryModel>, DbErr> {
+        let mut query = TxHistory::find();
+
+        if let Some(account_id) = account_id {
+            query = query.filter(tx_history::Column::AccountId.eq(account_id));
+        }
+
+        query.order_by_asc(tx_history::Column::Id).all(connection).await
+    }
+}
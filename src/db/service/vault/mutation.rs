@@ -0,0 +1,19 @@
+use sea_orm::{ActiveValue::NotSet, ConnectionTrait, DbErr, EntityTrait, Set};
+
+use crate::db::entities::prelude::*;
+
+pub struct Mutation;
+
+impl Mutation {
+    pub async fn create_vault(
+        salt: String,
+        check: String,
+        connection: &impl ConnectionTrait,
+    ) -> Result<i32, DbErr> {
+        let vault = VaultActiveModel { id: NotSet, salt: Set(salt), check: Set(check) };
+
+        let res = Vault::insert(vault).exec(connection).await?;
+
+        Ok(res.last_insert_id)
+    }
+}
@@ -0,0 +1,11 @@
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+
+use crate::db::entities::prelude::*;
+
+pub struct Query;
+
+impl Query {
+    pub async fn get_vault(connection: &impl ConnectionTrait) -> Result<Option<VaultModel>, DbErr> {
+        Vault::find().one(connection).await
+    }
+}
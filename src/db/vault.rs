@@ -0,0 +1,89 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use dialoguer::{theme::ColorfulTheme, Password};
+use sea_orm::{DbConn, TransactionTrait};
+
+use crate::utils::crypto::{generate_salt, is_encrypted, set_cipher, KeyCipher};
+
+use super::{
+    constants::PASSPHRASE_ENV_VAR,
+    service::prelude::{AccountMutation, AccountQuery, VaultMutation, VaultQuery},
+};
+
+/// Known value encrypted with the passphrase on vault creation, used to tell a wrong passphrase
+/// apart before any key is touched
+const VAULT_CHECK_VALUE: &str = "eclipse-farmer";
+
+/// Derives the key encryption key from the passphrase and makes it available for the current run.
+/// On the first run the vault is created and keys stored in plaintext by older versions get
+/// encrypted
+pub async fn unlock_vault(conn: &DbConn) -> eyre::Result<()> {
+    let cipher = match VaultQuery::get_vault(conn).await? {
+        Some(vault) => {
+            let passphrase = read_passphrase(false)?;
+            let salt = BASE64_STANDARD.decode(&vault.salt)?;
+            let cipher = KeyCipher::derive(&passphrase, &salt)?;
+
+            if cipher.decrypt(&vault.check)?.expose() != VAULT_CHECK_VALUE {
+                eyre::bail!("Failed to unlock the vault, the passphrase is wrong");
+            }
+
+            cipher
+        }
+        None => {
+            tracing::info!("Creating a new vault, the passphrase will be required on every run");
+
+            let passphrase = read_passphrase(true)?;
+            let salt = generate_salt();
+            let cipher = KeyCipher::derive(&passphrase, &salt)?;
+
+            VaultMutation::create_vault(
+                BASE64_STANDARD.encode(salt),
+                cipher.encrypt(VAULT_CHECK_VALUE)?,
+                conn,
+            )
+            .await?;
+
+            cipher
+        }
+    };
+
+    let plaintext_accounts = AccountQuery::get_all_accounts(conn)
+        .await?
+        .into_iter()
+        .filter(|account| {
+            !is_encrypted(account.evm_private_key.expose()) ||
+                !is_encrypted(account.eclipse_private_key.expose())
+        })
+        .collect::<Vec<_>>();
+
+    if !plaintext_accounts.is_empty() {
+        let count = plaintext_accounts.len();
+        let txn = conn.begin().await?;
+
+        for account in plaintext_accounts {
+            AccountMutation::encrypt_private_keys(account, &cipher, &txn).await?;
+        }
+
+        txn.commit().await?;
+        tracing::info!("Encrypted private keys of {count} account(s) stored in plaintext");
+    }
+
+    set_cipher(cipher);
+
+    Ok(())
+}
+
+fn read_passphrase(new_vault: bool) -> eyre::Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(passphrase);
+    }
+
+    let theme = ColorfulTheme::default();
+    let mut prompt = Password::with_theme(&theme).with_prompt("Vault passphrase");
+
+    if new_vault {
+        prompt = prompt.with_confirmation("Repeat the passphrase", "Passphrases don't match");
+    }
+
+    Ok(prompt.interact()?)
+}
@@ -1,7 +1,14 @@
-use modules::menu;
+use std::sync::Arc;
+
+use clap::Parser;
+use cli::Cli;
+use config::Config;
+use db::{establish_connection, vault::unlock_vault};
+use modules::{menu, run_command};
 
 use utils::logger::init_default_logger;
 
+mod cli;
 mod coinlore;
 mod config;
 mod db;
@@ -11,11 +18,37 @@ mod utils;
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
-    let _guard = init_default_logger();
+    let cli = Cli::parse();
+    let guard = init_default_logger();
+
+    if let Err(e) = run(cli).await {
+        tracing::error!("Execution stopped with error: {e}");
 
-    if let Err(e) = menu().await {
-        tracing::error!("Execution stopped with error: {e}")
+        // flush the file logger before exiting with a non-zero code
+        drop(guard);
+        std::process::exit(1);
     }
 
     Ok(())
 }
+
+async fn run(cli: Cli) -> eyre::Result<()> {
+    let mut config = Config::read_from_file(&cli.config).await?;
+    cli.overrides.apply(&mut config);
+
+    if config.general.dry_run {
+        tracing::warn!("Dry run mode is enabled, transactions will be simulated instead of sent");
+    }
+
+    let config = Arc::new(config);
+    let conn = establish_connection().await?;
+
+    if cli.command.as_ref().is_none_or(|command| command.requires_keys()) {
+        unlock_vault(&conn).await?;
+    }
+
+    match cli.command {
+        Some(command) => run_command(command, config, conn).await,
+        None => menu(config, conn).await,
+    }
+}
@@ -0,0 +1,201 @@
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use sea_orm::{DbConn, TransactionTrait};
+
+use crate::{
+    db::{
+        generate::{parse_tags, validate_proxy},
+        service::prelude::*,
+    },
+    modules::warmup::action::ACTIONS,
+};
+
+use super::status::{print_accounts, print_status};
+
+pub async fn accounts_menu(conn: DbConn) -> eyre::Result<()> {
+    loop {
+        let options = vec![
+            "List accounts",
+            "List accounts with a tag",
+            "Search by address",
+            "Manage an account",
+            "Back",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choice:")
+            .items(&options)
+            .default(0)
+            .interact()?;
+
+        match selection {
+            0 => print_status(None, &conn).await?,
+            1 => {
+                let tags = TagQuery::get_all_tags(&conn).await?;
+                if tags.is_empty() {
+                    tracing::warn!("No tags found");
+                    continue;
+                }
+
+                let names = tags.iter().map(|tag| tag.name.as_str()).collect::<Vec<_>>();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Tag:")
+                    .items(&names)
+                    .default(0)
+                    .interact()?;
+
+                print_status(Some(names[selection]), &conn).await?;
+            }
+            2 => {
+                let query: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Address, part of it or label")
+                    .interact_text()?;
+
+                let accounts = AccountQuery::search_accounts(query.trim(), &conn).await?;
+
+                match accounts.is_empty() {
+                    true => tracing::warn!("No accounts match `{query}`"),
+                    false => print_accounts(&accounts, &conn).await?,
+                }
+            }
+            3 => {
+                let account_id: i32 = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Account ID")
+                    .interact_text()?;
+
+                match AccountQuery::find_account_by_id(account_id, &conn).await {
+                    Ok(_) => manage_account(account_id, &conn).await?,
+                    Err(e) => tracing::error!("{e}"),
+                }
+            }
+            4 => break,
+            _ => tracing::error!("Invalid selection"),
+        }
+    }
+
+    Ok(())
+}
+
+async fn manage_account(account_id: i32, conn: &DbConn) -> eyre::Result<()> {
+    loop {
+        let account = AccountQuery::find_account_by_id(account_id, conn).await?;
+        let goal = AccountGoalQuery::get_account_goal_by_id(account_id, conn).await?;
+
+        let tags = TagQuery::get_account_tags(account_id, conn)
+            .await?
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect::<Vec<_>>();
+
+        print_accounts(std::slice::from_ref(&account), conn).await?;
+        println!("Proxy: {}", account.proxy.as_deref().unwrap_or("-"));
+        println!("Tags: {}\n", if tags.is_empty() { "-".to_string() } else { tags.join(", ") });
+
+        let toggle = match account.is_active {
+            true => "Pause the account",
+            false => "Activate the account",
+        };
+        let options = vec![toggle, "Edit goals", "Edit proxy", "Edit tags", "Back"];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choice:")
+            .items(&options)
+            .default(0)
+            .interact()?;
+
+        match selection {
+            0 => {
+                AccountMutation::set_active(account_id, !account.is_active, conn).await?;
+                tracing::info!(
+                    "Account #{account_id} is now {}",
+                    if account.is_active { "paused" } else { "active" }
+                );
+            }
+            1 => {
+                let progress = ActionProgressQuery::get_account_progress(account_id, conn).await?;
+
+                let mut targets = vec![];
+                for action in ACTIONS {
+                    let target = progress
+                        .iter()
+                        .find(|progress| progress.action_name == action.name())
+                        .map_or(0, |progress| progress.target);
+
+                    let target: u32 = Input::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!(
+                            "{} goal, [{}] section",
+                            action.name(),
+                            action.config_section()
+                        ))
+                        .default(target as u32)
+                        .interact_text()?;
+
+                    targets.push((action.name(), target));
+                }
+
+                let volume_usd: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Volume goal in USD, leave empty to remove")
+                    .with_initial_text(
+                        goal.volume_usd.map(|volume| volume.to_string()).unwrap_or_default(),
+                    )
+                    .allow_empty(true)
+                    .interact_text()?;
+
+                let volume_usd = match volume_usd.trim() {
+                    "" => None,
+                    volume => match volume.parse::<f64>() {
+                        Ok(volume) if volume >= 0.0 => Some(volume),
+                        _ => {
+                            tracing::error!("Invalid volume `{volume}`");
+                            continue;
+                        }
+                    },
+                };
+
+                let txn = conn.begin().await?;
+                for (action_name, target) in targets {
+                    ActionProgressMutation::set_target(
+                        account_id,
+                        action_name,
+                        target as i32,
+                        &txn,
+                    )
+                    .await?;
+                }
+                AccountGoalMutation::update_account_goal(account_id, volume_usd, &txn).await?;
+                txn.commit().await?;
+
+                tracing::info!("Goals of account #{account_id} updated");
+            }
+            2 => {
+                let proxy: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Proxy, leave empty to remove")
+                    .allow_empty(true)
+                    .interact_text()?;
+
+                let proxy = Some(proxy.trim().to_string()).filter(|proxy| !proxy.is_empty());
+
+                if let Some(Err(e)) = proxy.as_deref().map(validate_proxy) {
+                    tracing::error!("Invalid proxy: {e}");
+                    continue;
+                }
+
+                AccountMutation::set_proxy(account_id, proxy, conn).await?;
+                tracing::info!("Proxy of account #{account_id} updated");
+            }
+            3 => {
+                let input: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Comma separated tags, leave empty to remove all")
+                    .with_initial_text(tags.join(", "))
+                    .allow_empty(true)
+                    .interact_text()?;
+
+                TagMutation::set_account_tags(account_id, &parse_tags(&input), conn).await?;
+                tracing::info!("Tags of account #{account_id} updated");
+            }
+            4 => break,
+            _ => tracing::error!("Invalid selection"),
+        }
+    }
+
+    Ok(())
+}
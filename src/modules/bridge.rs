@@ -1,16 +1,28 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use crate::{
-    config::Config,
-    db::service::prelude::*,
-    onchain::evm::{eclipse_bridge::deposit, types::Token},
-    utils::misc::{pretty_sleep, random_in_range},
+    config::{AmountStrategy, Bridge, Config},
+    db::{
+        entities::{
+            prelude::BridgeStateModel,
+            sea_orm_active_enums::{BridgeStatus, Chain, TxAction, TxStatus},
+        },
+        service::prelude::*,
+    },
+    onchain::evm::{
+        client::{EvmClient, FeeCeiling},
+        eclipse_bridge::{
+            estimate_deposit_fee, get_bridge_limits, sign_deposit, simulate_deposit, BridgeLimits,
+        },
+        types::Token,
+    },
+    utils::misc::random_in_range,
 };
 use alloy::{
     network::Ethereum,
     primitives::{
-        utils::{format_units, parse_ether},
-        U256,
+        utils::{format_units, parse_ether, parse_units},
+        Address, TxHash, U256,
     },
     providers::{builder, Provider, RootProvider},
     rpc::client::ClientBuilder,
@@ -20,102 +32,249 @@ use alloy::{
         Transport,
     },
 };
-use rand::{seq::SliceRandom, thread_rng};
+use chrono::Utc;
 use reqwest::Client;
-use sea_orm::{ConnectionTrait, DatabaseConnection, DbConn, DbErr};
-use tokio::task::JoinSet;
+use sea_orm::{ConnectionTrait, DbConn, DbErr};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use tokio::time::Instant;
 
-pub async fn bridge_mode(connection: DbConn, config: Arc<Config>) -> eyre::Result<()> {
-    let spawn_task = |handles: &mut JoinSet<_>,
-                      batch_ids: Vec<_>,
-                      conn: DatabaseConnection,
-                      provider: Arc<RootProvider<RetryBackoffService<Http<Client>>>>,
-                      balance_range: [u32; 2],
-                      sleep_range: [u32; 2]| {
-        handles.spawn(async move {
-            let thread_res =
-                process_batch(batch_ids.clone(), conn, provider, balance_range, sleep_range).await;
-            (batch_ids, thread_res)
-        })
-    };
+use super::{
+    dry_run::{DryRunEntry, DryRunReport},
+    runner::{run_module, Module, TaskOutcome},
+};
 
-    let retry_layer = RetryBackoffLayer::new(10, 2, 500);
-    let client = ClientBuilder::default()
-        .layer(retry_layer)
-        .transport(Http::new(config.general.mainnet_rpc_url.parse()?), false);
-    let provider = Arc::new(builder::<Ethereum>().on_provider(RootProvider::new(client)));
+type MainnetProvider = RootProvider<RetryBackoffService<Http<Client>>>;
 
-    let thread_count = config.general.thread_count as usize;
-    let balance_range = config.bridge.balance_percentage_range;
-    let sleep_delay = config.bridge.wallet_sleep_delay_range;
+/// Native ETH on Eclipse has 9 decimals, one lamport is this many wei
+const WEI_PER_LAMPORT: u64 = 1_000_000_000;
 
-    let accounts_states_ids =
-        match BridgeModuleStateQuery::get_accounts_with_unbridged_state(&connection).await {
-            Ok(states) => states.into_iter().map(|state| state.id).collect::<Vec<_>>(),
-            Err(DbErr::RecordNotFound(_)) => {
-                tracing::info!("Funds were bridged for all wallets!");
-                return Ok(());
+/// How often a wallet waiting for cheaper gas looks at the fees again
+const GAS_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a wallet waits for its deposit to be mined. A deposit sent with capped fees can stay
+/// pending until the fees drop, it is left `submitted` and settled by the later checks
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// How often the Eclipse balances of the deposits in flight are checked
+const ARRIVAL_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+struct BridgeModule {
+    conn: DbConn,
+    provider: Arc<MainnetProvider>,
+    eclipse_client: Arc<RpcClient>,
+    config: Arc<Config>,
+    settings: BridgeSettings,
+}
+
+/// Everything a deposit is checked against, read once before the run
+struct BridgeSettings {
+    amount: BridgeAmount,
+    gas_limit: Option<GasLimit>,
+    limits: BridgeLimits,
+}
+
+/// `bridge.AMOUNT_STRATEGY` with its settings, checked once before the run
+enum BridgeAmount {
+    Percentage([u32; 2]),
+    Fixed([f64; 2]),
+    AllButReserve(U256),
+    TargetBalance(U256),
+}
+
+impl BridgeAmount {
+    fn from_config(config: &Bridge) -> eyre::Result<Self> {
+        let required =
+            |key: &str| eyre::eyre!("`bridge.{key}` is required by the selected AMOUNT_STRATEGY");
+
+        match config.amount_strategy {
+            AmountStrategy::Percentage => Ok(Self::Percentage(config.balance_percentage_range)),
+            AmountStrategy::Fixed => {
+                let range =
+                    config.fixed_amount_range.ok_or_else(|| required("FIXED_AMOUNT_RANGE"))?;
+                range.into_iter().try_for_each(|amount| eth_to_wei(amount).map(|_| ()))?;
+
+                Ok(Self::Fixed(range))
+            }
+            AmountStrategy::AllButReserve => {
+                let reserve = config.reserve.ok_or_else(|| required("RESERVE"))?;
+
+                Ok(Self::AllButReserve(eth_to_wei(reserve)?))
             }
-            Err(e) => eyre::bail!(e),
+            AmountStrategy::TargetBalance => {
+                let target = config
+                    .target_eclipse_balance
+                    .ok_or_else(|| required("TARGET_ECLIPSE_BALANCE"))?;
+
+                Ok(Self::TargetBalance(eth_to_wei(target)?))
+            }
+        }
+    }
+
+    /// Amount in wei before the minimum deposit and the balance are checked
+    async fn pick(
+        &self,
+        balance: U256,
+        eclipse_client: &RpcClient,
+        eclipse_pubkey: &Pubkey,
+    ) -> eyre::Result<U256> {
+        match self {
+            Self::Percentage(range) => {
+                Ok(balance * U256::from(random_in_range(*range)) / U256::from(100))
+            }
+            Self::Fixed(range) => eth_to_wei(random_in_range(*range)),
+            Self::AllButReserve(reserve) => Ok(balance.saturating_sub(*reserve)),
+            Self::TargetBalance(target) => {
+                let lamports = eclipse_client.get_balance(eclipse_pubkey).await?;
+                let eclipse_balance = U256::from(lamports) * U256::from(WEI_PER_LAMPORT);
+
+                Ok(target.saturating_sub(eclipse_balance))
+            }
+        }
+    }
+}
+
+/// `bridge.MAX_BASE_FEE_GWEI` and `bridge.MAX_PRIORITY_FEE_GWEI` with the time a wallet waits for
+/// the fees to get under them
+struct GasLimit {
+    ceiling: FeeCeiling,
+    wait_timeout: Option<Duration>,
+}
+
+impl GasLimit {
+    /// `None` when neither fee is limited
+    fn from_config(config: &Bridge) -> eyre::Result<Option<Self>> {
+        if config.max_base_fee_gwei.is_none() && config.max_priority_fee_gwei.is_none() {
+            return Ok(None);
+        }
+
+        let ceiling = FeeCeiling {
+            max_base_fee: config.max_base_fee_gwei.map(gwei_to_wei).transpose()?,
+            max_priority_fee: config.max_priority_fee_gwei.map(gwei_to_wei).transpose()?,
         };
 
-    let chunk_size = accounts_states_ids.len() / thread_count
-        + if accounts_states_ids.len() % thread_count != 0 { 1 } else { 0 };
+        Ok(Some(Self {
+            ceiling,
+            wait_timeout: config.gas_wait_timeout_secs.map(Duration::from_secs),
+        }))
+    }
+}
 
-    let mut handles = JoinSet::new();
+fn gwei_to_wei(amount: f64) -> eyre::Result<u128> {
+    if !amount.is_finite() || amount < 0.0 {
+        eyre::bail!("Invalid gwei amount: {amount}");
+    }
+
+    let wei = parse_units(&amount.to_string(), "gwei")?.get_absolute();
 
-    for batch in accounts_states_ids.chunks(chunk_size) {
-        let conn = connection.clone();
-        let provider = provider.clone();
-        let batch_ids = batch.to_vec();
+    wei.try_into().map_err(|_| eyre::eyre!("Gwei amount is too large: {amount}"))
+}
 
-        spawn_task(&mut handles, batch_ids, conn, provider, balance_range, sleep_delay);
+fn eth_to_wei(amount: f64) -> eyre::Result<U256> {
+    if !amount.is_finite() || amount < 0.0 {
+        eyre::bail!("Invalid ETH amount: {amount}");
     }
 
-    while let Some(res) = handles.join_next().await {
-        let (batch_ids, thread_res) = res.unwrap();
+    Ok(parse_ether(&amount.to_string())?)
+}
 
-        if let Err(e) = thread_res {
-            tracing::error!("Thread execution stopped with error: {e}. Restarting the thread...");
-            let provider = provider.clone();
-            let conn = connection.clone();
-            spawn_task(&mut handles, batch_ids, conn, provider, balance_range, sleep_delay);
+impl Module for BridgeModule {
+    fn name(&self) -> &'static str {
+        "Bridge"
+    }
+
+    async fn pending_ids(&self) -> eyre::Result<Vec<i32>> {
+        match BridgeModuleStateQuery::get_accounts_with_unbridged_state(
+            self.config.general.tag.as_deref(),
+            &self.conn,
+        )
+        .await
+        {
+            Ok(states) => Ok(states.into_iter().map(|state| state.id).collect()),
+            Err(DbErr::RecordNotFound(_)) => Ok(vec![]),
+            Err(e) => Err(e.into()),
         }
     }
 
-    tracing::info!("Funds were bridged for all wallets!");
+    async fn execute(
+        &self,
+        account_id: i32,
+        report: Option<&DryRunReport>,
+    ) -> eyre::Result<TaskOutcome> {
+        bridge_funds(
+            &self.conn,
+            account_id,
+            self.provider.clone(),
+            &self.eclipse_client,
+            &self.settings,
+            report,
+        )
+        .await?;
 
-    Ok(())
+        Ok(TaskOutcome::Done)
+    }
+
+    fn sleep_range(&self) -> [u32; 2] {
+        self.config.bridge.wallet_sleep_delay_range
+    }
 }
 
-async fn process_batch<C, P, T>(
-    batch: Vec<i32>,
-    conn: C,
-    provider: Arc<P>,
-    balance_range: [u32; 2],
-    sleep_range: [u32; 2],
-) -> eyre::Result<()>
-where
-    C: ConnectionTrait + Clone,
-    P: Provider<T, Ethereum>,
-    T: Transport + Clone,
-{
-    loop {
-        let leftover_batch_ids =
-            BridgeModuleStateQuery::get_unbridged_state_ids_by_ids(&batch, &conn).await;
+pub async fn bridge_mode(connection: DbConn, config: Arc<Config>) -> eyre::Result<()> {
+    let retry_layer = RetryBackoffLayer::new(10, 2, 500);
+    let client = ClientBuilder::default()
+        .layer(retry_layer)
+        .transport(Http::new(config.general.mainnet_rpc_url.parse()?), false);
+    let provider = Arc::new(builder::<Ethereum>().on_provider(RootProvider::new(client)));
 
-        match leftover_batch_ids {
-            Ok(mut ids) => {
-                ids.shuffle(&mut thread_rng());
+    let eclipse_client = Arc::new(RpcClient::new(config.general.eclipse_rpc_url.to_string()));
+    let amount = BridgeAmount::from_config(&config.bridge)?;
+    let gas_limit = GasLimit::from_config(&config.bridge)?;
 
-                for id in ids {
-                    bridge_funds(conn.clone(), id, provider.clone(), balance_range).await?;
-                    pretty_sleep(sleep_range, false).await;
-                }
-            }
-            Err(_) => {
-                break;
-            }
+    if !config.general.dry_run {
+        reconcile_deposits(&connection, &provider, &config).await?;
+    }
+
+    let limits = get_bridge_limits(provider.clone()).await?;
+    if limits.paused {
+        tracing::warn!(
+            "Bridge | The Eclipse bridge is paused, no deposits can be made until it resumes"
+        );
+        return watch_deposits(&connection, &provider, &eclipse_client, &config, false).await;
+    }
+
+    tracing::info!(
+        "Bridge | Deposits from {} ETH{} are accepted",
+        format_units(limits.min_deposit, 18)?,
+        match limits.max_deposit {
+            Some(max_deposit) => format!(" to {} ETH", format_units(max_deposit, 18)?),
+            None => String::new(),
+        }
+    );
+
+    let settings = BridgeSettings { amount, gas_limit, limits };
+    let module = BridgeModule {
+        conn: connection.clone(),
+        provider: provider.clone(),
+        eclipse_client: eclipse_client.clone(),
+        config: config.clone(),
+        settings,
+    };
+
+    if config.general.dry_run {
+        return run_module(module, config).await;
+    }
+
+    // a daemon never finishes its run, so the deposits are watched alongside it
+    match config.general.daemon {
+        true => {
+            tokio::try_join!(
+                run_module(module, config.clone()),
+                watch_deposits(&connection, &provider, &eclipse_client, &config, true)
+            )?;
+        }
+        false => {
+            run_module(module, config.clone()).await?;
+            watch_deposits(&connection, &provider, &eclipse_client, &config, false).await?;
         }
     }
 
@@ -123,52 +282,496 @@ where
 }
 
 async fn bridge_funds<C, P, T>(
-    conn: C,
+    conn: &C,
     id: i32,
     provider: Arc<P>,
-    balance_range: [u32; 2],
+    eclipse_client: &RpcClient,
+    settings: &BridgeSettings,
+    report: Option<&DryRunReport>,
 ) -> eyre::Result<()>
 where
     C: ConnectionTrait,
     P: Provider<T, Ethereum>,
     T: Transport + Clone,
 {
-    let account = AccountQuery::find_account_by_id(id, &conn).await?;
-    let client = account.to_evm_client(provider);
+    let BridgeSettings { amount: strategy, gas_limit, limits } = settings;
+    let gas_limit = gas_limit.as_ref();
+
+    if report.is_none() {
+        // a retry after a deposit that failed midway must not send a second one
+        let state = BridgeModuleStateQuery::get_account_bridge_state_by_id(id, conn).await?;
+        let state = state.filter(|state| {
+            matches!(state.status, Some(BridgeStatus::Submitted | BridgeStatus::NeedsReview))
+        });
+        if let Some(state) = state {
+            if reconcile_deposit(conn, provider.as_ref(), &state).await?.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let client =
+        account.to_evm_client(provider)?.with_fee_ceiling(gas_limit.map(|limit| limit.ceiling));
     let client_address = client.address();
     let eclipse_pubkey = account.eclipse_pubkey();
+    let mut logs = vec![];
+
+    if let Some(gas_limit) = gas_limit {
+        match report {
+            Some(_) => {
+                let fees = client.get_network_fees().await?;
+                if let Some(reason) = gas_limit.ceiling.exceeded_by(&fees)? {
+                    logs.push(format!("{reason}, the bridge would wait for cheaper gas"));
+                }
+            }
+            None if !wait_for_gas(&client, gas_limit).await? => {
+                let reason = format!(
+                    "Deferred: the fees stayed above the ceiling for {}s",
+                    gas_limit.wait_timeout.unwrap_or_default().as_secs()
+                );
+
+                tracing::warn!("{client_address} | Skipping the wallet. {reason}");
+                BridgeModuleStateMutation::set_skip_reason(id, &reason, conn).await?;
+
+                return Ok(());
+            }
+            None => {}
+        }
+    }
 
     let balance = client.get_token_balance(Token::ETH, None).await?;
-    let percentage = random_in_range(balance_range);
-    let mut amount = balance * U256::from(percentage) / U256::from(100);
+    let mut amount = strategy.pick(balance, eclipse_client, &eclipse_pubkey).await?;
+
+    if amount.is_zero() && matches!(strategy, BridgeAmount::TargetBalance(_)) {
+        tracing::info!("{client_address} | {eclipse_pubkey} already holds the target balance");
+        if report.is_none() {
+            BridgeModuleStateMutation::set_deposit_status(id, Some(BridgeStatus::ArrivedL2), conn)
+                .await?;
+        }
+        return Ok(());
+    }
+
     let divisor = U256::from(10).pow(U256::from(11));
     amount = (amount / divisor) * divisor;
     let mut ui_amount = format_units(amount, 18)?;
 
-    let min_amount = parse_ether("0.002")?;
-    if amount < min_amount {
+    let clamped = limits.clamp(amount);
+    if clamped != amount {
         tracing::warn!(
-            "Amount is lower than min bridge amount: {ui_amount} < {}",
-            format_units(min_amount, 18)?
+            "{client_address} | {ui_amount} ETH is outside of the bridge deposit limits, using {} \
+             ETH instead",
+            format_units(clamped, 18)?
         );
-        amount = min_amount;
+        amount = clamped;
         ui_amount = format_units(amount, 18)?;
     }
 
+    // the gas can only be estimated for a deposit the balance covers
+    let fee = match amount <= balance {
+        true => Some(estimate_deposit_fee(&client, eclipse_pubkey, amount).await?),
+        false => None,
+    };
+
+    if fee.is_none_or(|fee| amount + fee > balance) {
+        let reason = format!(
+            "Underfunded: the balance of {} ETH doesn't cover {ui_amount} ETH plus up to {} ETH \
+             of gas",
+            format_units(balance, 18)?,
+            fee.map_or(Ok("?".to_string()), |fee| format_units(fee, 18))?
+        );
+
+        match report {
+            Some(report) => report.record(DryRunEntry {
+                account_id: id,
+                wallet: client_address.to_string(),
+                action: "Bridge",
+                result: Err(reason),
+                logs,
+            }),
+            None => {
+                tracing::warn!("{client_address} | Skipping the wallet. {reason}");
+                BridgeModuleStateMutation::set_skip_reason(id, &reason, conn).await?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let Some(report) = report {
+        let result = match simulate_deposit(&client, eclipse_pubkey, amount).await {
+            Ok(simulation) => {
+                logs.push(format!("eth_call output: {}", simulation.output));
+
+                Ok(format!(
+                    "Would bridge {ui_amount} ETH to {eclipse_pubkey}, gas limit: {}, max fee: {} ETH",
+                    simulation.gas_limit,
+                    format_units(simulation.max_fee(), 18)?
+                ))
+            }
+            Err(e) => Err(e.to_string()),
+        };
+
+        report.record(DryRunEntry {
+            account_id: id,
+            wallet: client_address.to_string(),
+            action: "Bridge",
+            result,
+            logs,
+        });
+
+        return Ok(());
+    }
+
     tracing::info!("{client_address} | Bridging {ui_amount} ETH to {eclipse_pubkey}");
 
-    match deposit(client, eclipse_pubkey, amount).await {
-        Ok(res) => {
-            match res {
+    // the deposit shows up on Eclipse as an increase over this balance
+    let eclipse_balance = eclipse_client.get_balance(&eclipse_pubkey).await?;
+
+    let record =
+        TxRecord::new(id, Chain::Mainnet, TxAction::Bridge).token_in(Token::ETH.symbol, amount);
+
+    // a dropped deposit keeps its nonce, if it is taken now the dropped one may have been mined
+    let dropped_nonce = BridgeModuleStateQuery::get_account_bridge_state_by_id(id, conn)
+        .await?
+        .and_then(|state| state.nonce.filter(|_| state.status.is_none()));
+
+    let tx = match sign_deposit(&client, eclipse_pubkey, amount).await {
+        Ok(tx) => tx,
+        Err(e) => {
+            let record = record.error(&e);
+            TxHistoryMutation::create_tx_record(record, TxStatus::Failed, conn).await?;
+            eyre::bail!("Failed to sign a transaction: {e}")
+        }
+    };
+
+    if let Some(nonce) = dropped_nonce.filter(|nonce| *nonce as u64 != tx.nonce) {
+        let reason = format!(
+            "Needs review: nonce {nonce} of the dropped deposit was taken by another transaction, \
+             it may be a copy of the deposit"
+        );
+        tracing::warn!(
+            "{client_address} | {reason}. The wallet won't be bridged again until its status is \
+             changed"
+        );
+        BridgeModuleStateMutation::set_needs_review(id, &reason, conn).await?;
+
+        return Ok(());
+    }
+
+    let hash = tx.hash;
+    BridgeModuleStateMutation::set_submitted(id, hash, tx.nonce, amount, eclipse_balance, conn)
+        .await?;
+
+    let sent = async {
+        client.broadcast_transaction(tx).await?;
+        client.wait_for_transaction(hash, RECEIPT_TIMEOUT).await
+    }
+    .await;
+
+    match sent {
+        Ok(None) => {
+            tracing::warn!(
+                "{client_address} | Deposit {hash} wasn't mined in {}s, it is left submitted and \
+                 checked again until it is",
+                RECEIPT_TIMEOUT.as_secs()
+            );
+        }
+        Ok(Some(tx)) => {
+            let record = record.tx_hash(tx.hash).fee(Some(tx.fee));
+
+            match tx.success {
                 true => {
-                    BridgeModuleStateMutation::set_funds_bridged(id, &conn).await?;
-                    tracing::info!("{} | Bridge sent successfully", client_address)
+                    TxHistoryMutation::create_tx_record(record, TxStatus::Success, conn).await?;
+                    BridgeModuleStateMutation::set_deposit_status(
+                        id,
+                        Some(BridgeStatus::ConfirmedL1),
+                        conn,
+                    )
+                    .await?;
+                    tracing::info!(
+                        "{client_address} | Bridge confirmed on mainnet, waiting for it to arrive \
+                         on Eclipse"
+                    )
+                }
+                false => {
+                    let record = record.error("Transaction reverted");
+                    TxHistoryMutation::create_tx_record(record, TxStatus::Failed, conn).await?;
+                    BridgeModuleStateMutation::set_deposit_status(id, None, conn).await?;
+                    eyre::bail!("Transaction was sent but failed")
                 }
-                false => eyre::bail!("Transaction was sent but failed"),
             };
         }
-        Err(e) => tracing::error!("Failed to send a transaction {e}"),
+        // the deposit may have reached the mempool anyway, it stays submitted and its history is
+        // written once the reconciliation settles it
+        Err(e) => eyre::bail!("Failed to send deposit {hash}, it is left submitted: {e}"),
     }
 
     Ok(())
 }
+
+/// Settles the deposits a previous run left `submitted`, e.g. because it stopped before their
+/// receipt came in. Returns how many of them are still pending
+async fn reconcile_deposits(
+    conn: &DbConn,
+    provider: &MainnetProvider,
+    config: &Config,
+) -> eyre::Result<usize> {
+    // the deposits left for a review are still looked up, one of their hashes may get mined
+    let deposits = BridgeModuleStateQuery::get_deposits(
+        config.general.tag.as_deref(),
+        &[BridgeStatus::Submitted, BridgeStatus::NeedsReview],
+        conn,
+    )
+    .await?;
+    let mut pending = 0;
+
+    for deposit in &deposits {
+        match reconcile_deposit(conn, provider, deposit).await {
+            Ok(Some(BridgeStatus::Submitted)) => pending += 1,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!(
+                    "Bridge | Failed to reconcile the deposit of account #{}: {e}",
+                    deposit.id
+                );
+                if deposit.status == Some(BridgeStatus::Submitted) {
+                    pending += 1;
+                }
+            }
+        }
+    }
+
+    Ok(pending)
+}
+
+/// Looks every transaction signed with the nonce of the submitted deposit up on mainnet. A mined
+/// one confirms the deposit or, if it reverted, clears it for another try. While none of them is
+/// known and the nonce is still free, the deposit is cleared as dropped and the next one reuses the
+/// nonce, so at most one of them can be mined. A nonce taken by anything else may be a sped up copy
+/// of the deposit, so it is left for a manual review. Returns the status the deposit is left in
+async fn reconcile_deposit<C, P, T>(
+    conn: &C,
+    provider: &P,
+    deposit: &BridgeStateModel,
+) -> eyre::Result<Option<BridgeStatus>>
+where
+    C: ConnectionTrait,
+    P: Provider<T, Ethereum>,
+    T: Transport + Clone,
+{
+    let mut tx_hashes = match deposit.nonce {
+        Some(nonce) => {
+            BridgeModuleStateQuery::get_deposit_tx_hashes(deposit.id, nonce, conn).await?
+        }
+        None => vec![],
+    };
+    if let Some(tx_hash) = deposit.tx_hash.as_ref().filter(|hash| !tx_hashes.contains(hash)) {
+        tx_hashes.push(tx_hash.clone());
+    }
+    if tx_hashes.is_empty() {
+        eyre::bail!("The submitted deposit has no transaction hash");
+    }
+    let tx_hashes =
+        tx_hashes.iter().map(|hash| TxHash::from_str(hash)).collect::<Result<Vec<_>, _>>()?;
+
+    let account = AccountQuery::find_account_by_id(deposit.id, conn).await?;
+    let address = Address::from_str(&account.evm_address)?;
+
+    for &tx_hash in &tx_hashes {
+        let Some(receipt) = provider.get_transaction_receipt(tx_hash).await? else {
+            continue;
+        };
+
+        let fee = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+        let record = TxRecord::new(deposit.id, Chain::Mainnet, TxAction::Bridge)
+            .token_in(Token::ETH.symbol, deposit.amount.as_deref().unwrap_or_default())
+            .tx_hash(tx_hash)
+            .fee(Some(fee));
+
+        let status = match receipt.status() {
+            true => {
+                TxHistoryMutation::create_tx_record(record, TxStatus::Success, conn).await?;
+                BridgeModuleStateMutation::set_tx_hash(deposit.id, tx_hash, conn).await?;
+                tracing::info!("{address} | Deposit {tx_hash} was mined, it won't be sent again");
+                Some(BridgeStatus::ConfirmedL1)
+            }
+            false => {
+                let record = record.error("Transaction reverted");
+                TxHistoryMutation::create_tx_record(record, TxStatus::Failed, conn).await?;
+                tracing::warn!(
+                    "{address} | Deposit {tx_hash} reverted, the wallet will be bridged again"
+                );
+                None
+            }
+        };
+
+        BridgeModuleStateMutation::set_deposit_status(deposit.id, status, conn).await?;
+        return Ok(status);
+    }
+
+    for &tx_hash in &tx_hashes {
+        if provider.get_transaction_by_hash(tx_hash).await?.is_some() {
+            tracing::info!("{address} | Deposit {tx_hash} is still pending");
+            return Ok(Some(BridgeStatus::Submitted));
+        }
+    }
+
+    let nonce = provider.get_transaction_count(address).await?;
+    let reason = match deposit.nonce {
+        Some(deposit_nonce) if nonce <= deposit_nonce as u64 => {
+            tracing::warn!(
+                "{address} | Deposit {} is unknown to the network and its nonce {deposit_nonce} \
+                 is still free. The wallet will be bridged again with the same nonce",
+                deposit.tx_hash.as_deref().unwrap_or_default()
+            );
+            BridgeModuleStateMutation::set_dropped(deposit.id, conn).await?;
+
+            return Ok(None);
+        }
+        Some(deposit_nonce) => format!(
+            "Needs review: nonce {deposit_nonce} was taken by a transaction other than the \
+             deposit, it may be a copy of it"
+        ),
+        None => "Needs review: the deposit is unknown to the network and has no nonce recorded"
+            .to_string(),
+    };
+
+    if deposit.status != Some(BridgeStatus::NeedsReview) {
+        tracing::warn!(
+            "{address} | {reason}. The wallet won't be bridged again until its status is changed"
+        );
+        BridgeModuleStateMutation::set_needs_review(deposit.id, &reason, conn).await?;
+    }
+
+    Ok(Some(BridgeStatus::NeedsReview))
+}
+
+/// Checks the Eclipse balances of the deposits confirmed on mainnet every
+/// [`ARRIVAL_POLL_INTERVAL`] until none is left in flight, settling the submitted ones along the
+/// way. Stuck deposits are checked but not waited for. With `keep_alive` it never returns
+async fn watch_deposits(
+    conn: &DbConn,
+    provider: &MainnetProvider,
+    eclipse_client: &RpcClient,
+    config: &Config,
+    keep_alive: bool,
+) -> eyre::Result<()> {
+    let timeout = chrono::Duration::seconds(config.bridge.arrival_timeout_secs as i64);
+
+    loop {
+        let mut in_flight = reconcile_deposits(conn, provider, config).await?;
+
+        let deposits = BridgeModuleStateQuery::get_deposits(
+            config.general.tag.as_deref(),
+            &[BridgeStatus::ConfirmedL1, BridgeStatus::Stuck],
+            conn,
+        )
+        .await?;
+
+        for deposit in &deposits {
+            match check_arrival(conn, eclipse_client, deposit, timeout).await {
+                Ok(Some(BridgeStatus::ConfirmedL1)) => in_flight += 1,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!(
+                        "Bridge | Failed to check the deposit of account #{}: {e}",
+                        deposit.id
+                    );
+                    in_flight += 1;
+                }
+            }
+        }
+
+        if in_flight == 0 && !keep_alive {
+            return Ok(());
+        }
+
+        if in_flight > 0 {
+            tracing::info!(
+                "Bridge | Waiting for {in_flight} deposit(s) to arrive on Eclipse, checking again \
+                 in {}s",
+                ARRIVAL_POLL_INTERVAL.as_secs()
+            );
+        }
+
+        tokio::time::sleep(ARRIVAL_POLL_INTERVAL).await;
+    }
+}
+
+/// Marks the deposit as arrived once the Eclipse balance grew by its amount, or as stuck once it
+/// was confirmed on mainnet longer than `timeout` ago. Returns the status the deposit is left in
+async fn check_arrival(
+    conn: &DbConn,
+    eclipse_client: &RpcClient,
+    deposit: &BridgeStateModel,
+    timeout: chrono::Duration,
+) -> eyre::Result<Option<BridgeStatus>> {
+    let account = AccountQuery::find_account_by_id(deposit.id, conn).await?;
+    let eclipse_pubkey = account.eclipse_pubkey();
+
+    let amount = U256::from_str(deposit.amount.as_deref().unwrap_or("0"))?;
+    let expected = deposit.eclipse_balance_before.unwrap_or_default() as u64 +
+        (amount / U256::from(WEI_PER_LAMPORT)).to::<u64>();
+
+    let balance = eclipse_client.get_balance(&eclipse_pubkey).await?;
+    let tx_hash = deposit.tx_hash.as_deref().unwrap_or_default();
+
+    if balance >= expected {
+        BridgeModuleStateMutation::set_deposit_status(
+            deposit.id,
+            Some(BridgeStatus::ArrivedL2),
+            conn,
+        )
+        .await?;
+        tracing::info!("{eclipse_pubkey} | Deposit {tx_hash} arrived on Eclipse");
+
+        return Ok(Some(BridgeStatus::ArrivedL2));
+    }
+
+    let overdue =
+        deposit.confirmed_at.is_some_and(|confirmed_at| Utc::now() - confirmed_at > timeout);
+    if deposit.status == Some(BridgeStatus::ConfirmedL1) && overdue {
+        BridgeModuleStateMutation::set_deposit_status(deposit.id, Some(BridgeStatus::Stuck), conn)
+            .await?;
+        tracing::warn!(
+            "{eclipse_pubkey} | Deposit {tx_hash} hasn't arrived on Eclipse in {}s, marked as stuck",
+            timeout.num_seconds()
+        );
+
+        return Ok(Some(BridgeStatus::Stuck));
+    }
+
+    Ok(deposit.status)
+}
+
+/// Checks the fees every [`GAS_POLL_INTERVAL`] until they are within the ceiling. Returns `false`
+/// once the wait timeout runs out first
+async fn wait_for_gas<P, T>(client: &EvmClient<P, T>, gas_limit: &GasLimit) -> eyre::Result<bool>
+where
+    P: Provider<T, Ethereum>,
+    T: Transport + Clone,
+{
+    let deadline = gas_limit.wait_timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let fees = client.get_network_fees().await?;
+        let Some(reason) = gas_limit.ceiling.exceeded_by(&fees)? else {
+            return Ok(true);
+        };
+
+        let now = Instant::now();
+        let delay = match deadline {
+            Some(deadline) if deadline <= now => return Ok(false),
+            Some(deadline) => GAS_POLL_INTERVAL.min(deadline - now),
+            None => GAS_POLL_INTERVAL,
+        };
+
+        tracing::info!(
+            "{} | {reason}, waiting for cheaper gas. Checking again in {}s",
+            client.address(),
+            delay.as_secs()
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
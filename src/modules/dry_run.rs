@@ -0,0 +1,54 @@
+use std::sync::{Arc, Mutex};
+
+pub struct DryRunEntry {
+    pub account_id: i32,
+    pub wallet: String,
+    pub action: &'static str,
+    /// Expected outcome of the action or the reason it would fail
+    pub result: Result<String, String>,
+    pub logs: Vec<String>,
+}
+
+/// Collects the simulated actions of every worker so they can be printed once the mode finishes
+#[derive(Clone, Default)]
+pub struct DryRunReport {
+    entries: Arc<Mutex<Vec<DryRunEntry>>>,
+}
+
+impl DryRunReport {
+    pub fn record(&self, entry: DryRunEntry) {
+        match &entry.result {
+            Ok(details) => {
+                tracing::info!("{} | [DRY RUN] {}: {details}", entry.wallet, entry.action)
+            }
+            Err(e) => {
+                tracing::warn!("{} | [DRY RUN] {} would fail: {e}", entry.wallet, entry.action)
+            }
+        }
+
+        self.entries.lock().expect("Report lock to not be poisoned").push(entry);
+    }
+
+    pub fn print(&self) {
+        let mut entries = self.entries.lock().expect("Report lock to not be poisoned");
+        entries.sort_by_key(|entry| entry.account_id);
+
+        println!("\nDry run report, {} simulated action(s):", entries.len());
+
+        for entry in entries.iter() {
+            let (status, details) = match &entry.result {
+                Ok(details) => ("OK", details),
+                Err(e) => ("FAIL", e),
+            };
+
+            println!(
+                "#{} {} | {} | {status} | {details}",
+                entry.account_id, entry.wallet, entry.action
+            );
+
+            for log in &entry.logs {
+                println!("    {log}");
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use sea_orm::{DbConn, TransactionTrait};
+
+use crate::{
+    config::Config,
+    db::service::prelude::*,
+    modules::warmup::action::{get_needed_warmup_actions, WarmupAction, ACTIONS},
+    utils::misc::random_in_range,
+};
+
+/// Raises the goals of every account by a random amount from the current config ranges. The volume
+/// goal is kept as is when `lifinity.VOLUME_USD_RANGE` is not set
+pub async fn top_up_goals(config: &Config, conn: &DbConn) -> eyre::Result<()> {
+    update_goals(
+        config,
+        conn,
+        |action, target| target + random_in_range(action.target_range(config)) as i32,
+        |volume_usd| match config.lifinity.volume_usd_range.map(random_in_range) {
+            Some(volume) => Some(volume_usd.unwrap_or_default() + volume),
+            None => volume_usd,
+        },
+    )
+    .await
+}
+
+/// Rolls new goals from the current config ranges, the progress of the accounts is kept
+pub async fn reroll_goals(config: &Config, conn: &DbConn) -> eyre::Result<()> {
+    update_goals(
+        config,
+        conn,
+        |action, _| random_in_range(action.target_range(config)) as i32,
+        |_| config.lifinity.volume_usd_range.map(random_in_range),
+    )
+    .await
+}
+
+/// Applies `new_target` to every registered action and `new_volume` to the volume goal of the
+/// accounts matching `general.TAG`. Accounts that were disabled because they had reached the old
+/// goal are reactivated when the new one leaves work to do, accounts paused by hand before reaching
+/// their goal stay paused
+async fn update_goals<T, V>(
+    config: &Config,
+    conn: &DbConn,
+    new_target: T,
+    new_volume: V,
+) -> eyre::Result<()>
+where
+    T: Fn(&dyn WarmupAction, i32) -> i32,
+    V: Fn(Option<f64>) -> Option<f64>,
+{
+    let txn = conn.begin().await?;
+
+    let accounts = AccountQuery::get_accounts(config.general.tag.as_deref(), &txn).await?;
+    let goals = AccountGoalQuery::get_all_account_goals(&txn)
+        .await?
+        .into_iter()
+        .map(|goal| (goal.id, goal))
+        .collect::<HashMap<_, _>>();
+
+    let mut progress = HashMap::<_, Vec<_>>::new();
+    for row in ActionProgressQuery::get_all_progress(&txn).await? {
+        progress.entry(row.account_id).or_default().push(row);
+    }
+
+    let mut updated = 0;
+    let mut reactivated = 0;
+
+    for account in &accounts {
+        let Some(goal) = goals.get(&account.id) else {
+            tracing::warn!("Account #{} has no goal, skipping", account.id);
+            continue;
+        };
+        let old_progress = progress.remove(&account.id).unwrap_or_default();
+
+        let reached_old_goal = get_needed_warmup_actions(account, goal, &old_progress).is_empty();
+
+        let goal =
+            AccountGoalMutation::update_account_goal(account.id, new_volume(goal.volume_usd), &txn)
+                .await?;
+
+        let mut new_progress = vec![];
+        for action in ACTIONS {
+            let target = old_progress
+                .iter()
+                .find(|progress| progress.action_name == action.name())
+                .map_or(0, |progress| progress.target);
+
+            new_progress.push(
+                ActionProgressMutation::set_target(
+                    account.id,
+                    action.name(),
+                    new_target(*action, target),
+                    &txn,
+                )
+                .await?,
+            );
+        }
+        updated += 1;
+
+        let has_work_left = !get_needed_warmup_actions(account, &goal, &new_progress).is_empty();
+
+        if !account.is_active && reached_old_goal && has_work_left {
+            AccountMutation::set_active(account.id, true, &txn).await?;
+            reactivated += 1;
+        }
+    }
+
+    txn.commit().await?;
+
+    tracing::info!("Updated the goals of {updated} account(s), {reactivated} reactivated");
+
+    Ok(())
+}
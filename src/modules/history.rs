@@ -0,0 +1,51 @@
+use std::str::FromStr;
+
+use alloy::primitives::{utils::format_units, U256};
+use sea_orm::ConnectionTrait;
+
+use crate::db::{entities::sea_orm_active_enums::Chain, service::prelude::TxHistoryQuery};
+
+pub async fn print_history<C>(account_id: Option<i32>, conn: &C) -> eyre::Result<()>
+where
+    C: ConnectionTrait,
+{
+    let records = TxHistoryQuery::get_tx_history(account_id, conn).await?;
+
+    let mut mainnet_fees = U256::ZERO;
+    let mut eclipse_fees = U256::ZERO;
+
+    for record in &records {
+        let fee =
+            record.fee.as_deref().and_then(|fee| U256::from_str(fee).ok()).unwrap_or_default();
+
+        match record.chain {
+            Chain::Mainnet => mainnet_fees += fee,
+            Chain::Eclipse => eclipse_fees += fee,
+        }
+
+        println!(
+            "{} | #{} | {:?} | {:?} | {:?} | {} {} -> {} {} | fee: {} | {}{}",
+            record.created_at.format("%Y-%m-%d %H:%M:%S"),
+            record.account_id,
+            record.chain,
+            record.action,
+            record.status,
+            record.amount_in.as_deref().unwrap_or("-"),
+            record.token_in.as_deref().unwrap_or("-"),
+            record.amount_out.as_deref().unwrap_or("-"),
+            record.token_out.as_deref().unwrap_or("-"),
+            record.fee.as_deref().unwrap_or("-"),
+            record.tx_hash.as_deref().unwrap_or("-"),
+            record.error.as_ref().map(|e| format!(" | {e}")).unwrap_or_default(),
+        );
+    }
+
+    println!(
+        "\nTransactions: {} | Fees paid: {} ETH on mainnet, {} ETH on Eclipse",
+        records.len(),
+        format_units(mainnet_fees, 18)?,
+        format_units(eclipse_fees, 9)?
+    );
+
+    Ok(())
+}
@@ -1,14 +1,30 @@
 use crate::{
+    cli::{Command, DbCommand, GoalsCommand, PlanCommand},
     config::Config,
-    db::{erase::erase_all_tables, establish_connection, generate::generate_db},
+    db::generate::{generate_db, import_accounts_file},
 };
+use accounts::accounts_menu;
 use bridge::bridge_mode;
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use goals::{reroll_goals, top_up_goals};
+use history::print_history;
 use sea_orm::DbConn;
+use status::print_status;
 use std::sync::Arc;
-use warmup::warmup_mode;
+use warmup::{
+    plan::{approve_plan, discard_plan, export_plan, generate_plan, PLAN_EXPORT_FILE_PATH},
+    warmup_mode,
+};
 
+mod accounts;
 mod bridge;
+mod dry_run;
+mod goals;
+mod history;
+mod queue;
+mod runner;
+mod schedule;
+mod status;
 pub mod warmup;
 
 const LOGO: &str = r#"
@@ -30,15 +46,53 @@ const LOGO: &str = r#"
                      t.me/fragment_software
 "#;
 
-pub async fn menu() -> eyre::Result<()> {
-    let config = Arc::new(Config::read_default().await);
-    let conn = establish_connection().await?;
+pub async fn run_command(command: Command, config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
+    match command {
+        Command::Db { command } => match command {
+            DbCommand::Generate => {
+                generate_db(&config, &conn, true).await?;
+                tracing::info!("Database generated successfully");
+            }
+            DbCommand::Append => {
+                generate_db(&config, &conn, false).await?;
+                tracing::info!("Data added successfully");
+            }
+            DbCommand::Import { path, erase } => {
+                import_accounts_file(&config, &conn, path, erase).await?;
+                tracing::info!("Data imported successfully");
+            }
+        },
+        Command::Goals { command } => match command {
+            GoalsCommand::TopUp => top_up_goals(&config, &conn).await?,
+            GoalsCommand::Reroll => reroll_goals(&config, &conn).await?,
+        },
+        Command::Plan { command } => match command {
+            PlanCommand::Generate => generate_plan(&config, &conn).await?,
+            PlanCommand::Export { path } => export_plan(path, &conn).await?,
+            PlanCommand::Approve => approve_plan(&conn).await?,
+            PlanCommand::Discard => discard_plan(&conn).await?,
+        },
+        Command::Bridge => bridge_mode(conn, config).await?,
+        Command::Warmup => warmup_mode(conn, config).await?,
+        Command::Status => print_status(config.general.tag.as_deref(), &conn).await?,
+        Command::History { account_id } => print_history(account_id, &conn).await?,
+    }
+
+    Ok(())
+}
 
+pub async fn menu(config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
     println!("{LOGO}");
 
     loop {
-        let options =
-            vec!["Database menu", "Bridge mode (MAINNET -> ECLIPSE)", "Warmup mode", "Exit"];
+        let options = vec![
+            "Database menu",
+            "Accounts menu",
+            "Bridge mode (MAINNET -> ECLIPSE)",
+            "Warmup plan menu",
+            "Warmup mode",
+            "Exit",
+        ];
 
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Choice:")
@@ -49,9 +103,11 @@ pub async fn menu() -> eyre::Result<()> {
 
         match selection {
             0 => db_menu(config.clone(), conn.clone()).await?,
-            1 => bridge_mode(conn.clone(), config.clone()).await?,
-            2 => warmup_mode(conn.clone(), config.clone()).await?,
-            3 => {
+            1 => accounts_menu(conn.clone()).await?,
+            2 => bridge_mode(conn.clone(), config.clone()).await?,
+            3 => plan_menu(config.clone(), conn.clone()).await?,
+            4 => warmup_mode(conn.clone(), config.clone()).await?,
+            5 => {
                 return Ok(());
             }
             _ => tracing::error!("Invalid selection"),
@@ -61,8 +117,14 @@ pub async fn menu() -> eyre::Result<()> {
 
 async fn db_menu(config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
     loop {
-        let sub_options =
-            vec!["Generate a new database", "Append data to the existing database", "Back"];
+        let sub_options = vec![
+            "Generate a new database",
+            "Append data to the existing database",
+            "Import accounts from a CSV/JSON file",
+            "Top up the goals",
+            "Re-roll the goals",
+            "Back",
+        ];
 
         let sub_selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Choice:")
@@ -72,19 +134,27 @@ async fn db_menu(config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
             .unwrap();
 
         match sub_selection {
-            0 => {
-                erase_all_tables(&conn).await?;
-                if generate_db(&config, &conn).await.is_ok() {
-                    tracing::info!("Database generated successfully");
-                } else {
-                    tracing::error!("Failed to generate a database")
-                }
-            }
+            0 => match generate_db(&config, &conn, true).await {
+                Ok(()) => tracing::info!("Database generated successfully"),
+                Err(e) => tracing::error!("Failed to generate a database: {e}"),
+            },
             1 => {
-                generate_db(&config, &conn).await?;
+                generate_db(&config, &conn, false).await?;
                 tracing::info!("Data added successfully");
             }
             2 => {
+                let path: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Path to the .csv or .json file")
+                    .interact_text()?;
+
+                match import_accounts_file(&config, &conn, path, false).await {
+                    Ok(()) => tracing::info!("Data imported successfully"),
+                    Err(e) => tracing::error!("Failed to import accounts: {e}"),
+                }
+            }
+            3 => top_up_goals(&config, &conn).await?,
+            4 => reroll_goals(&config, &conn).await?,
+            5 => {
                 break;
             }
             _ => {
@@ -95,3 +165,42 @@ async fn db_menu(config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
 
     Ok(())
 }
+
+async fn plan_menu(config: Arc<Config>, conn: DbConn) -> eyre::Result<()> {
+    loop {
+        let sub_options = vec![
+            "Generate a new plan",
+            "Export the plan to a CSV file",
+            "Approve the plan",
+            "Discard the plan",
+            "Back",
+        ];
+
+        let sub_selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Choice:")
+            .items(&sub_options)
+            .default(0)
+            .interact()
+            .unwrap();
+
+        let result = match sub_selection {
+            0 => generate_plan(&config, &conn).await,
+            1 => export_plan(PLAN_EXPORT_FILE_PATH, &conn).await,
+            2 => approve_plan(&conn).await,
+            3 => discard_plan(&conn).await,
+            4 => {
+                break;
+            }
+            _ => {
+                tracing::error!("Invalid sub-selection.");
+                Ok(())
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("{e}");
+        }
+    }
+
+    Ok(())
+}
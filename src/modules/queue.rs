@@ -0,0 +1,131 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Mutex,
+    time::Duration,
+};
+
+use chrono::Utc;
+use sea_orm::prelude::DateTimeUtc;
+use tokio::time::Instant;
+
+/// Longest a worker waits before looking at the queue again while every task is backing off
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Longest a failed task waits before its next attempt, however many attempts it took
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+pub struct Task {
+    pub id: i32,
+    pub attempts: u32,
+    not_before: Instant,
+}
+
+/// Account ids shared by every worker of a mode. Workers pull the next due id, so a slow or
+/// failing account only ever occupies a single worker
+pub struct TaskQueue {
+    tasks: Mutex<VecDeque<Task>>,
+    /// Ids that are queued or being run, an id leaves once the account is done or out of retries
+    claimed: Mutex<HashSet<i32>>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    /// Workers wait for new tasks instead of stopping once the queue is empty
+    keep_alive: bool,
+}
+
+impl TaskQueue {
+    pub fn new(ids: Vec<i32>, max_retries: u32, retry_backoff_secs: u64, keep_alive: bool) -> Self {
+        let now = Instant::now();
+        let claimed = ids.iter().copied().collect();
+        let tasks = ids.into_iter().map(|id| Task { id, attempts: 0, not_before: now }).collect();
+
+        Self {
+            tasks: Mutex::new(tasks),
+            claimed: Mutex::new(claimed),
+            max_retries,
+            retry_backoff: Duration::from_secs(retry_backoff_secs),
+            keep_alive,
+        }
+    }
+
+    /// Returns the first task that is due, waiting for a backing off one if needed. Returns `None`
+    /// once the queue is empty, unless the queue is kept alive
+    pub async fn pop(&self) -> Option<Task> {
+        loop {
+            let next_due = {
+                let mut tasks = self.tasks.lock().expect("Queue lock to not be poisoned");
+                let now = Instant::now();
+
+                if let Some(index) = tasks.iter().position(|task| task.not_before <= now) {
+                    return tasks.remove(index);
+                }
+
+                match tasks.iter().map(|task| task.not_before).min() {
+                    Some(next_due) => next_due,
+                    None if self.keep_alive => now + MAX_POLL_INTERVAL,
+                    None => return None,
+                }
+            };
+
+            let wake_at = next_due.min(Instant::now() + MAX_POLL_INTERVAL);
+            tokio::time::sleep_until(wake_at).await;
+        }
+    }
+
+    /// Queues an account that is not queued or being run yet. Returns `false` when it already is
+    pub fn add(&self, id: i32) -> bool {
+        if !self.claimed.lock().expect("Queue lock to not be poisoned").insert(id) {
+            return false;
+        }
+
+        self.push(id);
+
+        true
+    }
+
+    /// Forgets an account that has nothing left to do, so [`TaskQueue::add`] can queue it again
+    pub fn finish(&self, id: i32) {
+        self.claimed.lock().expect("Queue lock to not be poisoned").remove(&id);
+    }
+
+    /// Puts the account back at the end of the queue with a clean retry counter
+    pub fn push(&self, id: i32) {
+        let task = Task { id, attempts: 0, not_before: Instant::now() };
+        self.tasks.lock().expect("Queue lock to not be poisoned").push_back(task);
+    }
+
+    /// Puts the account back into the queue, it is not handed out again before `not_before`
+    pub fn push_at(&self, id: i32, not_before: DateTimeUtc) {
+        let delay = (not_before - Utc::now()).to_std().unwrap_or_default();
+        let task = Task { id, attempts: 0, not_before: Instant::now() + delay };
+        self.tasks.lock().expect("Queue lock to not be poisoned").push_back(task);
+    }
+
+    /// Requeues a failed task with an exponential backoff, capped at [`MAX_RETRY_BACKOFF`]. Returns
+    /// `false` once the task ran out of retries and was dropped
+    pub fn retry(&self, mut task: Task) -> bool {
+        task.attempts += 1;
+
+        if task.attempts > self.max_retries {
+            self.finish(task.id);
+            return false;
+        }
+
+        let backoff = 2u32
+            .checked_pow(task.attempts - 1)
+            .and_then(|factor| self.retry_backoff.checked_mul(factor))
+            .map_or(MAX_RETRY_BACKOFF, |backoff| backoff.min(MAX_RETRY_BACKOFF));
+        task.not_before = Instant::now() + backoff;
+
+        tracing::warn!(
+            "Account #{} will be retried in {}s (attempt {}/{})",
+            task.id,
+            backoff.as_secs(),
+            task.attempts,
+            self.max_retries
+        );
+
+        self.tasks.lock().expect("Queue lock to not be poisoned").push_back(task);
+
+        true
+    }
+}
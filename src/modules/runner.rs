@@ -0,0 +1,180 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use rand::{seq::SliceRandom, thread_rng};
+use sea_orm::prelude::DateTimeUtc;
+use tokio::task::JoinSet;
+use tracing::Instrument;
+
+use crate::{config::Config, utils::misc::pretty_sleep};
+
+use super::{dry_run::DryRunReport, queue::TaskQueue, schedule::wait_for_active_hours};
+
+/// How often a daemon looks for accounts that became pending, e.g. newly imported ones
+const DAEMON_POLL_INTERVAL: Duration = Duration::from_secs(300);
+
+pub enum TaskOutcome {
+    /// The account has more work left and goes back to the end of the queue. `sleep_range`
+    /// replaces [`Module::sleep_range`] for the delay after this step
+    Continue { sleep_range: Option<[u32; 2]> },
+    /// Nothing was done, the account is not due before `until` and waits in the queue. The worker
+    /// moves on to the next account without sleeping
+    NotDue { until: DateTimeUtc },
+    /// Nothing left to do for the account in this run
+    Done,
+}
+
+/// A mode that runs the same per-account task over every pending account. Scheduling, retries,
+/// sleeps between accounts and the dry run report are handled by [`run_module`]
+pub trait Module: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    /// Ids of the accounts that still have work left
+    fn pending_ids(&self) -> impl Future<Output = eyre::Result<Vec<i32>>> + Send;
+
+    /// Runs the next step for the account. In dry run mode `report` is set and the step has to be
+    /// simulated without touching the database
+    fn execute(
+        &self,
+        account_id: i32,
+        report: Option<&DryRunReport>,
+    ) -> impl Future<Output = eyre::Result<TaskOutcome>> + Send;
+
+    /// Random delay between two accounts processed by the same worker, unless the step sets its own
+    fn sleep_range(&self) -> [u32; 2];
+}
+
+/// Runs the module until every pending account is done. As a daemon it never returns and keeps
+/// picking up accounts that become pending
+pub async fn run_module<M>(module: M, config: Arc<Config>) -> eyre::Result<()>
+where
+    M: Module,
+{
+    let name = module.name();
+    let general = &config.general;
+    // a dry run visits every account once, so it can't run as a daemon
+    let daemon = general.daemon && !general.dry_run;
+
+    let mut ids = module.pending_ids().await?;
+    if ids.is_empty() && !daemon {
+        tracing::info!("{name} | Nothing left to do for any wallet!");
+        return Ok(());
+    }
+    ids.shuffle(&mut thread_rng());
+
+    if daemon {
+        tracing::info!(
+            "{name} | Running as a daemon, looking for new wallets every {}s",
+            DAEMON_POLL_INTERVAL.as_secs()
+        );
+    }
+
+    let module = Arc::new(module);
+    let queue =
+        Arc::new(TaskQueue::new(ids, general.max_retries, general.retry_backoff_secs, daemon));
+    let report = general.dry_run.then(DryRunReport::default);
+
+    let mut handles = JoinSet::new();
+
+    for _ in 0..general.thread_count {
+        handles.spawn(process_queue(module.clone(), queue.clone(), report.clone(), config.clone()));
+    }
+
+    if daemon {
+        handles.spawn(watch_pending_ids(module.clone(), queue.clone()));
+    }
+
+    while let Some(res) = handles.join_next().await {
+        if let Err(e) = res {
+            tracing::error!("{name} | Worker stopped unexpectedly: {e}");
+        }
+    }
+
+    if let Some(report) = report {
+        report.print();
+        return Ok(());
+    }
+
+    match module.pending_ids().await?.len() {
+        0 => tracing::info!("{name} | Finished for all wallets!"),
+        left => tracing::warn!(
+            "{name} | Finished, {left} wallet(s) were skipped and are left for the next run"
+        ),
+    }
+
+    Ok(())
+}
+
+/// Queues the accounts that became pending since the last look, e.g. newly imported ones or ones
+/// whose goals were raised
+async fn watch_pending_ids<M>(module: Arc<M>, queue: Arc<TaskQueue>)
+where
+    M: Module,
+{
+    let name = module.name();
+
+    loop {
+        tokio::time::sleep(DAEMON_POLL_INTERVAL).await;
+
+        match module.pending_ids().await {
+            Ok(ids) => {
+                let added = ids.into_iter().filter(|id| queue.add(*id)).count();
+                if added > 0 {
+                    tracing::info!("{name} | Picked up {added} new wallet(s)");
+                }
+            }
+            Err(e) => tracing::error!("{name} | Failed to look for pending wallets: {e}"),
+        }
+    }
+}
+
+async fn process_queue<M>(
+    module: Arc<M>,
+    queue: Arc<TaskQueue>,
+    report: Option<DryRunReport>,
+    config: Arc<Config>,
+) where
+    M: Module,
+{
+    let name = module.name();
+
+    while let Some(task) = queue.pop().await {
+        if report.is_none() {
+            wait_for_active_hours(&config.schedule).await;
+        }
+
+        let id = task.id;
+        let span = tracing::info_span!("account", module = name, id);
+
+        let sleep_range = match module.execute(id, report.as_ref()).instrument(span).await {
+            // nothing is written to the database in dry run mode, so every account is visited once
+            Ok(TaskOutcome::Continue { sleep_range }) if report.is_none() => {
+                queue.push(id);
+                sleep_range
+            }
+            Ok(TaskOutcome::NotDue { until }) => {
+                tracing::info!("{name} | Account #{id} is due at {}", until.to_rfc3339());
+                queue.push_at(id, until);
+                continue;
+            }
+            Ok(_) => {
+                queue.finish(id);
+                None
+            }
+            Err(e) => {
+                tracing::error!("{name} | Account #{id} failed: {e}");
+
+                if !queue.retry(task) {
+                    tracing::error!(
+                        "{name} | Account #{id} is out of retries, skipping the wallet"
+                    );
+                }
+
+                None
+            }
+        };
+
+        if report.is_none() {
+            pretty_sleep(sleep_range.unwrap_or_else(|| module.sleep_range()), false).await;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use chrono::{Duration, Offset, Utc};
+use sea_orm::prelude::DateTimeUtc;
+
+use crate::{config::Schedule, utils::misc::random_in_range};
+
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// Share of the average gap a delay is rolled from, so accounts don't move in lockstep
+const SPREAD_JITTER_RANGE: [f64; 2] = [0.75, 1.25];
+
+/// Delay before the next action of an account with `total_actions` actions in its goals. The longer
+/// of the `SPREAD_DAYS` gap and the `MAX_ACTIONS_PER_DAY` gap wins. `None` when neither is set
+pub fn next_action_delay(schedule: &Schedule, total_actions: u32) -> Option<Duration> {
+    let spread = schedule.spread_days.map(|days| {
+        days as f64 * SECS_PER_DAY / total_actions.max(1) as f64 *
+            random_in_range(SPREAD_JITTER_RANGE)
+    });
+    let daily_limit = schedule
+        .max_actions_per_day
+        .filter(|count| *count > 0)
+        .map(|count| SECS_PER_DAY / count as f64);
+
+    let secs = match (spread, daily_limit) {
+        (Some(spread), Some(daily_limit)) => spread.max(daily_limit),
+        (spread, daily_limit) => spread.or(daily_limit)?,
+    };
+
+    Some(Duration::seconds(secs.ceil() as i64))
+}
+
+/// Time left until the next `ACTIVE_HOURS` window opens, `None` while inside one or when no windows
+/// are set
+pub fn time_until_active(schedule: &Schedule, now: DateTimeUtc) -> Option<Duration> {
+    if schedule.active_hours.is_empty() {
+        return None;
+    }
+
+    let time = match (schedule.timezone, schedule.utc_offset) {
+        (Some(timezone), _) => now.with_timezone(&timezone).time(),
+        (None, offset) => now.with_timezone(&offset.unwrap_or(Utc.fix())).time(),
+    };
+
+    if schedule.active_hours.iter().any(|window| window.contains(time)) {
+        return None;
+    }
+
+    schedule
+        .active_hours
+        .iter()
+        .map(|window| match window.start - time {
+            delay if delay < Duration::zero() => delay + Duration::days(1),
+            delay => delay,
+        })
+        .min()
+}
+
+/// Sleeps until the next `ACTIVE_HOURS` window when called outside of every window
+pub async fn wait_for_active_hours(schedule: &Schedule) {
+    while let Some(delay) = time_until_active(schedule, Utc::now()) {
+        tracing::info!(
+            "Outside of the active hours, resuming at {}",
+            (Utc::now() + delay).format("%Y-%m-%d %H:%M UTC")
+        );
+
+        tokio::time::sleep(delay.to_std().unwrap_or_default()).await;
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use sea_orm::ConnectionTrait;
+
+use crate::{
+    db::{
+        entities::{prelude::AccountModel, sea_orm_active_enums::BridgeStatus},
+        service::prelude::*,
+    },
+    modules::warmup::action::ACTIONS,
+};
+
+pub async fn print_status<C>(tag: Option<&str>, conn: &C) -> eyre::Result<()>
+where
+    C: ConnectionTrait,
+{
+    let accounts = AccountQuery::get_accounts(tag, conn).await?;
+
+    print_accounts(&accounts, conn).await
+}
+
+/// Prints the progress of the given accounts against their goals
+pub async fn print_accounts<C>(accounts: &[AccountModel], conn: &C) -> eyre::Result<()>
+where
+    C: ConnectionTrait,
+{
+    let (goals, bridge_states, progress) = tokio::try_join!(
+        AccountGoalQuery::get_all_account_goals(conn),
+        BridgeModuleStateQuery::get_all_bridge_states(conn),
+        ActionProgressQuery::get_all_progress(conn),
+    )?;
+
+    let goals = goals.into_iter().map(|goal| (goal.id, goal)).collect::<HashMap<_, _>>();
+    let bridge_states =
+        bridge_states.into_iter().map(|state| (state.id, state)).collect::<HashMap<_, _>>();
+    let progress = progress
+        .into_iter()
+        .map(|row| ((row.account_id, row.action_name.clone()), row))
+        .collect::<HashMap<_, _>>();
+
+    let mut header = format!(
+        "{:>5} | {:<16} | {:<42} | {:<44} | {:<6} | {:<9}",
+        "ID", "Label", "EVM address", "Eclipse address", "Active", "Bridged"
+    );
+    for action in ACTIONS {
+        header.push_str(&format!(" | {:<15}", action.name()));
+    }
+    header.push_str(&format!(" | {:<17} | {:<16}", "Volume, $", "Next action, UTC"));
+
+    println!("{header}");
+
+    for account in accounts {
+        let goal = goals.get(&account.id);
+        let bridged = match bridge_states.get(&account.id) {
+            Some(state) => match state.status {
+                Some(BridgeStatus::Submitted) => "submitted",
+                Some(BridgeStatus::ConfirmedL1) => "confirmed",
+                Some(BridgeStatus::ArrivedL2) => "arrived",
+                Some(BridgeStatus::Stuck) => "stuck",
+                Some(BridgeStatus::NeedsReview) => "review",
+                None if state.funds_bridged => "yes",
+                None if state.skip_reason.is_some() => "skipped",
+                None => "no",
+            },
+            None => "no",
+        };
+
+        let mut row = format!(
+            "{:>5} | {:<16} | {:<42} | {:<44} | {:<6} | {:<9}",
+            account.id,
+            account.label.as_deref().unwrap_or("-"),
+            account.evm_address,
+            account.eclipse_address,
+            account.is_active,
+            bridged,
+        );
+
+        for action in ACTIONS {
+            let (done, target) = progress
+                .get(&(account.id, action.name().to_string()))
+                .map_or((0, 0), |progress| (progress.done, progress.target));
+
+            row.push_str(&format!(" | {:<15}", format!("{done}/{target}")));
+        }
+
+        let volume_goal = goal
+            .and_then(|g| g.volume_usd)
+            .map_or("-".to_string(), |volume| format!("{volume:.2}"));
+        row.push_str(&format!(
+            " | {:<17}",
+            format!("{:.2}/{volume_goal}", account.swap_volume_usd)
+        ));
+
+        let next_action_at = account
+            .next_action_at
+            .map_or("-".to_string(), |at| at.format("%Y-%m-%d %H:%M").to_string());
+        row.push_str(&format!(" | {next_action_at:<16}"));
+
+        println!("{row}");
+    }
+
+    let active = accounts.iter().filter(|account| account.is_active).count();
+    let bridged = accounts
+        .iter()
+        .filter(|account| bridge_states.get(&account.id).is_some_and(|state| state.funds_bridged))
+        .count();
+
+    let total = accounts.len();
+
+    println!("\nTotal: {total} | Active: {active} | Bridged: {bridged}/{total}");
+
+    Ok(())
+}
@@ -1,5 +1,171 @@
-#[derive(Clone)]
-pub enum WarmupAction {
-    LifinitySwap,
-    UnderdogCreate,
+use std::{future::Future, pin::Pin};
+
+use rand::{seq::SliceRandom, thread_rng};
+use sea_orm::DbConn;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{
+    config::Config,
+    db::{
+        entities::{
+            prelude::{AccountGoalModel, AccountModel, ActionProgressModel},
+            sea_orm_active_enums::TxStatus,
+        },
+        service::prelude::{TxHistoryMutation, TxRecord},
+    },
+    onchain::eclipse::common::{
+        token::Token,
+        tx::{failed_tx_signature, TxSimulation},
+    },
+};
+
+use super::{lifinity::LifinitySwap, underdog::UnderdogCreate};
+
+pub type ActionFuture<'a, T> = Pin<Box<dyn Future<Output = eyre::Result<T>> + Send + 'a>>;
+
+/// Every action the warmup can pick from. The progress of an action is stored in the
+/// `action_progress` table under its name, so adding one needs no schema changes
+pub static ACTIONS: &[&dyn WarmupAction] = &[&LifinitySwap, &UnderdogCreate];
+
+#[derive(Clone, Copy)]
+pub struct ActionContext<'a> {
+    pub account: &'a AccountModel,
+    pub rpc_client: &'a RpcClient,
+    pub config: &'a Config,
+    pub conn: &'a DbConn,
+    /// Parameters of the scenario step or planned action being run, `None` when the action decides
+    /// everything on its own
+    pub params: Option<&'a toml::Table>,
+}
+
+/// What a wallet is expected to hold at a given point of a plan, updated by every planned action
+pub struct PlanState {
+    /// Token the next swap starts from
+    pub token: Token,
+}
+
+impl Default for PlanState {
+    /// Bridged wallets start with nothing but ETH
+    fn default() -> Self {
+        Self { token: Token::ETH }
+    }
+}
+
+pub trait WarmupAction: Send + Sync {
+    /// Key of the action in the `action_progress` table
+    fn name(&self) -> &'static str;
+
+    /// Section of the config holding the settings of the action
+    fn config_section(&self) -> &'static str;
+
+    /// Range the goal of a new account is rolled from
+    fn target_range(&self, config: &Config) -> [u32; 2];
+
+    /// Relative chance of the action being picked among the needed ones
+    fn weight(&self, config: &Config) -> u32;
+
+    /// Random delay before the worker moves on to its next account after running the action
+    fn sleep_range(&self, config: &Config) -> [u32; 2];
+
+    /// Whether the account still has to run the action to reach its goal
+    fn is_needed(
+        &self,
+        _account: &AccountModel,
+        _goal: &AccountGoalModel,
+        progress: &ActionProgressModel,
+    ) -> bool {
+        progress.done < progress.target
+    }
+
+    /// Validates the parameters of a scenario step before any step is run
+    fn check_params(&self, params: &toml::Table) -> eyre::Result<()> {
+        match params.keys().next() {
+            Some(key) => eyre::bail!("Unknown parameter `{key}`, `{}` takes none", self.name()),
+            None => Ok(()),
+        }
+    }
+
+    /// Fixes everything the action would otherwise decide at run time, so a plan shows exactly
+    /// what will be done. `params` are the scenario step parameters, if any
+    fn plan_params(
+        &self,
+        _config: &Config,
+        params: &toml::Table,
+        _state: &mut PlanState,
+    ) -> eyre::Result<toml::Table> {
+        Ok(params.clone())
+    }
+
+    /// Runs the action and records its transaction, the progress counter is increased by the
+    /// caller on success
+    fn execute<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, ()>;
+
+    /// Simulates the action without touching the database, returns a description of the
+    /// expected outcome
+    fn simulate<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, (String, TxSimulation)>;
+}
+
+pub fn find_action(name: &str) -> Option<&'static dyn WarmupAction> {
+    ACTIONS.iter().find(|action| action.name() == name).copied()
+}
+
+/// Actions the account still needs, an action without a progress row has no goal
+pub fn get_needed_warmup_actions(
+    account: &AccountModel,
+    goal: &AccountGoalModel,
+    progress: &[ActionProgressModel],
+) -> Vec<&'static dyn WarmupAction> {
+    ACTIONS
+        .iter()
+        .copied()
+        .filter(|action| {
+            let progress = progress
+                .iter()
+                .find(|progress| progress.action_name == action.name())
+                .cloned()
+                .unwrap_or_else(|| ActionProgressModel {
+                    account_id: account.id,
+                    action_name: action.name().to_string(),
+                    target: 0,
+                    done: 0,
+                });
+
+            action.is_needed(account, goal, &progress)
+        })
+        .collect()
+}
+
+/// Picks one of the needed actions by their weights. When every needed action has a zero weight
+/// they are picked uniformly, so an action with a zero weight only runs once the others are done
+pub fn get_random_warmup_action(
+    account: &AccountModel,
+    goal: &AccountGoalModel,
+    progress: &[ActionProgressModel],
+    config: &Config,
+) -> Option<&'static dyn WarmupAction> {
+    let actions = get_needed_warmup_actions(account, goal, progress);
+    let mut rng = thread_rng();
+
+    match actions.choose_weighted(&mut rng, |action| action.weight(config)) {
+        Ok(action) => Some(*action),
+        Err(_) => actions.choose(&mut rng).copied(),
+    }
+}
+
+/// Adds the action to the transaction history. A failed write is only logged, the transaction is
+/// on chain either way and the progress of the action still has to be counted
+pub async fn record_tx(record: TxRecord, status: TxStatus, conn: &DbConn) {
+    if let Err(e) = TxHistoryMutation::create_tx_record(record, status, conn).await {
+        tracing::error!("Failed to save the transaction to the history: {e}");
+    }
+}
+
+/// Records a failed action, with the signature of its transaction if it was sent
+pub async fn record_failed_tx(record: TxRecord, error: &eyre::Report, conn: &DbConn) {
+    let record = match failed_tx_signature(error) {
+        Some(signature) => record.tx_hash(signature),
+        None => record,
+    };
+
+    record_tx(record.error(error), TxStatus::Failed, conn).await
 }
@@ -0,0 +1,302 @@
+use reqwest::Proxy;
+use sea_orm::{DbConn, DbErr};
+use serde::Deserialize;
+use solana_sdk::signer::Signer;
+
+use crate::{
+    coinlore::api::get_tickers_usd_value,
+    config::Config,
+    db::{
+        entities::{
+            prelude::{AccountGoalModel, AccountModel, ActionProgressModel},
+            sea_orm_active_enums::{Chain, TxAction, TxStatus},
+        },
+        service::prelude::{AccountGoalQuery, AccountMutation, ActionProgressQuery, TxRecord},
+    },
+    onchain::eclipse::{
+        common::{
+            token::Token,
+            tx::{get_tx_details, TxSimulation},
+            utils::{get_token_balance, get_token_with_largest_balance},
+        },
+        lifinity::{
+            swap::{amount_out_from_logs, simulate_swap, swap, SwapSimulation},
+            utils::determine_pool_pubkey,
+        },
+    },
+    utils::misc::random_in_range,
+};
+
+use super::action::{
+    record_failed_tx, record_tx, ActionContext, ActionFuture, PlanState, WarmupAction,
+};
+
+pub struct LifinitySwap;
+
+/// Scenario step or planned action parameters, anything missing falls back to the config and the
+/// wallet balances
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SwapParams {
+    token_in: Option<String>,
+    token_out: Option<String>,
+    balance_percentage_range: Option<[u32; 2]>,
+    /// Exact share of the balance, set by plans. Takes precedence over the range
+    balance_percentage: Option<u32>,
+}
+
+struct SwapPlan {
+    token_in: Token,
+    token_out: Token,
+    amount_in: u64,
+}
+
+impl SwapParams {
+    fn parse(params: &toml::Table) -> eyre::Result<Self> {
+        let params = toml::Value::Table(params.clone()).try_into::<SwapParams>()?;
+
+        let token_in = params.token_in.as_deref().map(Token::from_symbol).transpose()?;
+        let token_out = params.token_out.as_deref().map(Token::from_symbol).transpose()?;
+
+        if let (Some(token_in), Some(token_out)) = (token_in, token_out) {
+            if determine_pool_pubkey(&token_in.mint, &token_out.mint).is_none() {
+                eyre::bail!("No Lifinity pool for {} -> {}", token_in.symbol, token_out.symbol);
+            }
+        }
+
+        if params.balance_percentage.is_some_and(|percentage| percentage > 100) {
+            eyre::bail!("`balance_percentage` can't be above 100");
+        }
+
+        Ok(params)
+    }
+}
+
+impl WarmupAction for LifinitySwap {
+    fn name(&self) -> &'static str {
+        "lifinity_swap"
+    }
+
+    fn config_section(&self) -> &'static str {
+        "lifinity"
+    }
+
+    fn target_range(&self, config: &Config) -> [u32; 2] {
+        config.lifinity.swaps_count_range
+    }
+
+    fn weight(&self, config: &Config) -> u32 {
+        config.lifinity.weight
+    }
+
+    fn sleep_range(&self, config: &Config) -> [u32; 2] {
+        config.lifinity.wallet_sleep_delay_range
+    }
+
+    /// Swaps continue until both the count and the volume goals are met
+    fn is_needed(
+        &self,
+        account: &AccountModel,
+        goal: &AccountGoalModel,
+        progress: &ActionProgressModel,
+    ) -> bool {
+        let volume_left = goal.volume_usd.is_some_and(|volume| account.swap_volume_usd < volume);
+
+        progress.done < progress.target || volume_left
+    }
+
+    fn check_params(&self, params: &toml::Table) -> eyre::Result<()> {
+        SwapParams::parse(params).map(|_| ())
+    }
+
+    /// Without a `token_in` the plan swaps whatever the previous planned swap bought, starting
+    /// from ETH, instead of the token with the largest balance
+    fn plan_params(
+        &self,
+        config: &Config,
+        params: &toml::Table,
+        state: &mut PlanState,
+    ) -> eyre::Result<toml::Table> {
+        let params = SwapParams::parse(params)?;
+
+        let token_in = match params.token_in.as_deref() {
+            Some(symbol) => Token::from_symbol(symbol)?,
+            None => state.token,
+        };
+        let token_out = match params.token_out.as_deref() {
+            Some(symbol) => Token::from_symbol(symbol)?,
+            None => Token::get_lifinity_paired_token(&token_in),
+        };
+
+        if determine_pool_pubkey(&token_in.mint, &token_out.mint).is_none() {
+            eyre::bail!("No Lifinity pool for {} -> {}", token_in.symbol, token_out.symbol);
+        }
+
+        let percentage = params.balance_percentage.unwrap_or_else(|| {
+            random_in_range(
+                params.balance_percentage_range.unwrap_or(config.lifinity.balance_percentage_range),
+            )
+        });
+
+        state.token = token_out;
+
+        let mut planned = toml::Table::new();
+        planned.insert("token_in".to_string(), token_in.symbol.into());
+        planned.insert("token_out".to_string(), token_out.symbol.into());
+        planned.insert("balance_percentage".to_string(), (percentage as i64).into());
+
+        Ok(planned)
+    }
+
+    fn execute<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, ()> {
+        Box::pin(execute_lifinity_swap(ctx))
+    }
+
+    fn simulate<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, (String, TxSimulation)> {
+        Box::pin(simulate_lifinity_swap(ctx))
+    }
+}
+
+/// Picks the pair and the amount of the swap, step parameters take precedence over the
+/// config. Without a `token_in` the token with the largest USD value is swapped
+async fn plan_swap(ctx: ActionContext<'_>) -> eyre::Result<SwapPlan> {
+    let ActionContext { account, rpc_client, config, params, .. } = ctx;
+
+    let params = params.map(SwapParams::parse).transpose()?.unwrap_or_default();
+    let owner = account.eclipse_pubkey();
+
+    let (token_in, balance) = match params.token_in.as_deref() {
+        Some(symbol) => {
+            let token = Token::from_symbol(symbol)?;
+            (token, get_token_balance(rpc_client, &owner, &token).await?)
+        }
+        None => {
+            get_token_with_largest_balance(rpc_client, &owner, account.proxy().as_ref()).await?
+        }
+    };
+    let token_out = match params.token_out.as_deref() {
+        Some(symbol) => Token::from_symbol(symbol)?,
+        None => Token::get_lifinity_paired_token(&token_in),
+    };
+
+    if determine_pool_pubkey(&token_in.mint, &token_out.mint).is_none() {
+        eyre::bail!("No Lifinity pool for {} -> {}", token_in.symbol, token_out.symbol);
+    }
+
+    let balance = Token::to_amount(&token_in, balance);
+
+    let percentage = params.balance_percentage.unwrap_or_else(|| {
+        random_in_range(
+            params.balance_percentage_range.unwrap_or(config.lifinity.balance_percentage_range),
+        )
+    });
+    let amount_in = balance * percentage as u64 / 100;
+
+    if amount_in == 0 {
+        eyre::bail!("Nothing to swap, the {} balance is empty", token_in.symbol);
+    }
+
+    Ok(SwapPlan { token_in, token_out, amount_in })
+}
+
+async fn simulate_lifinity_swap(ctx: ActionContext<'_>) -> eyre::Result<(String, TxSimulation)> {
+    let SwapPlan { token_in, token_out, amount_in } = plan_swap(ctx).await?;
+    let rpc_client = ctx.rpc_client;
+    let keypair = ctx.account.eclise_keypair()?;
+
+    let SwapSimulation { amount_out, simulation } =
+        simulate_swap(rpc_client, &keypair, &token_in.mint, &token_out.mint, amount_in).await?;
+
+    let details = format!(
+        "Would swap {} {} to ~{} {}, compute units: {}",
+        Token::to_ui_amount(&token_in, amount_in),
+        token_in.symbol,
+        Token::to_ui_amount(&token_out, amount_out),
+        token_out.symbol,
+        simulation.units_consumed.unwrap_or_default()
+    );
+
+    Ok((details, simulation))
+}
+
+/// USD value of `amount` of `token` at the current price
+async fn usd_value(token: &Token, amount: u64, proxy: Option<&Proxy>) -> eyre::Result<f64> {
+    let prices = get_tickers_usd_value(proxy).await?;
+    let price =
+        prices.get(token).ok_or_else(|| eyre::eyre!("No USD price found for {}", token.symbol))?;
+
+    Ok(Token::to_ui_amount(token, amount) * price)
+}
+
+async fn execute_lifinity_swap(ctx: ActionContext<'_>) -> eyre::Result<()> {
+    let SwapPlan { token_in, token_out, amount_in } = plan_swap(ctx).await?;
+    let ActionContext { account, rpc_client, conn, params, .. } = ctx;
+    let keypair = account.eclise_keypair()?;
+    let proxy = account.proxy();
+
+    // the USD value only counts towards the volume goal
+    let goal = AccountGoalQuery::get_account_goal_by_id(account.id, conn).await?;
+    let volume_usd = match goal.volume_usd {
+        Some(_) => match usd_value(&token_in, amount_in, proxy.as_ref()).await {
+            Ok(volume_usd) => Some(volume_usd),
+            // a swap picked only for the volume would be spent for nothing, so it fails and the
+            // retry limit applies instead
+            Err(e) if params.is_none() && !count_left(account.id, conn).await? => {
+                eyre::bail!("Failed to price the swap, it is only needed for the volume: {e}")
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "{} | Failed to price the swap, it won't count towards the volume goal: {e}",
+                    keypair.pubkey()
+                );
+                None
+            }
+        },
+        None => None,
+    };
+
+    tracing::info!(
+        "{} | Swapping {} {} to {}",
+        keypair.pubkey(),
+        Token::to_ui_amount(&token_in, amount_in),
+        token_in.symbol,
+        token_out.symbol
+    );
+    let record = TxRecord::new(account.id, Chain::Eclipse, TxAction::LifinitySwap)
+        .token_in(token_in.symbol, amount_in);
+
+    match swap(rpc_client, &keypair, &token_in.mint, &token_out.mint, amount_in).await {
+        Ok(receipt) => {
+            let details = get_tx_details(rpc_client, &receipt.signature).await;
+            let amount_out =
+                amount_out_from_logs(&details.logs).unwrap_or(receipt.expected_amount_out);
+
+            let record = record
+                .tx_hash(receipt.signature)
+                .token_out(token_out.symbol, Some(amount_out))
+                .fee(details.fee);
+
+            record_tx(record, TxStatus::Success, conn).await;
+
+            if let Some(volume_usd) = volume_usd {
+                AccountMutation::add_swap_volume(account.id, volume_usd, conn).await?;
+            }
+        }
+        Err(e) => {
+            let record = record.token_out(token_out.symbol, None::<u64>);
+            record_failed_tx(record, &e, conn).await;
+            eyre::bail!("Swap failed: {e}")
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether the account still has swaps left for the count goal
+async fn count_left(account_id: i32, conn: &DbConn) -> eyre::Result<bool> {
+    match ActionProgressQuery::get_action_progress(account_id, LifinitySwap.name(), conn).await {
+        Ok(progress) => Ok(progress.done < progress.target),
+        Err(DbErr::RecordNotFound(_)) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
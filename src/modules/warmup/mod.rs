@@ -1,197 +1,483 @@
-use super::warmup::action::WarmupAction;
+use super::{
+    dry_run::{DryRunEntry, DryRunReport},
+    runner::{run_module, Module, TaskOutcome},
+    schedule::next_action_delay,
+};
 use crate::{
     config::Config,
     db::{
-        entities::prelude::*,
-        service::prelude::{AccountGoalQuery, AccountMutation, AccountQuery},
-    },
-    onchain::eclipse::{
-        common::{token::Token, utils::get_token_with_largest_balance},
-        lifinity::swap::swap,
-        underdog::create::create_collection,
+        entities::prelude::{AccountModel, PlanModel, PlannedActionModel},
+        service::prelude::{
+            AccountGoalQuery, AccountMutation, AccountQuery, ActionProgressMutation,
+            ActionProgressQuery, BridgeModuleStateQuery, PlanMutation, PlanQuery,
+            ScenarioProgressMutation, ScenarioProgressQuery,
+        },
     },
-    utils::misc::{pretty_sleep, random_in_range},
 };
-use rand::{seq::SliceRandom, thread_rng};
-use sea_orm::{ConnectionTrait, DbConn, DbErr};
+use action::{
+    find_action, get_needed_warmup_actions, get_random_warmup_action, ActionContext, WarmupAction,
+};
+use chrono::Utc;
+use plan::{format_params, load_plan};
+use scenario::Scenario;
+use sea_orm::{DbConn, DbErr};
 use solana_client::nonblocking::rpc_client::RpcClient;
 
 use solana_sdk::signer::Signer;
 use std::sync::Arc;
-use tokio::task::JoinSet;
 
 pub mod action;
+pub mod lifinity;
+pub mod plan;
+mod scenario;
+pub mod underdog;
 
-pub async fn warmup_mode(connection: DbConn, config: Arc<Config>) -> eyre::Result<()> {
-    let spawn_task = |handles: &mut JoinSet<_>,
-                      batch: Vec<_>,
-                      conn: DbConn,
-                      rpc_client: Arc<RpcClient>,
-                      config| {
-        handles.spawn(async move {
-            let thread_res = process_batch(batch.clone(), conn, config, rpc_client).await;
-            (batch, thread_res)
-        })
-    };
+struct WarmupModule {
+    conn: DbConn,
+    rpc_client: Arc<RpcClient>,
+    config: Arc<Config>,
+    scenario: Option<Scenario>,
+    /// Approved plan with actions left, replaces both the scenario and the random picks
+    plan: Option<PlanModel>,
+}
 
-    let rpc_client = Arc::new(RpcClient::new(config.general.eclipse_rpc_url.to_string()));
-    let thread_count = config.general.thread_count as usize;
+impl Module for WarmupModule {
+    fn name(&self) -> &'static str {
+        "Warmup"
+    }
 
-    let accounts_ids = match AccountQuery::get_active_accounts(&connection).await {
-        Ok(account) => account.into_iter().map(|account| account.id).collect::<Vec<_>>(),
-        Err(DbErr::RecordNotFound(_)) => {
-            tracing::info!("No more active wallets left!");
-            return Ok(());
-        }
-        Err(e) => eyre::bail!(e),
-    };
+    async fn pending_ids(&self) -> eyre::Result<Vec<i32>> {
+        let ids =
+            match AccountQuery::get_active_accounts(self.config.general.tag.as_deref(), &self.conn)
+                .await
+            {
+                Ok(accounts) => accounts.into_iter().map(|account| account.id).collect::<Vec<_>>(),
+                Err(DbErr::RecordNotFound(_)) => return Ok(vec![]),
+                Err(e) => return Err(e.into()),
+            };
 
-    let chunk_size = accounts_ids.len() / thread_count +
-        if accounts_ids.len() % thread_count != 0 { 1 } else { 0 };
+        let ids = match self.config.general.require_bridge_arrival {
+            true => {
+                let arrived = BridgeModuleStateQuery::get_arrived_ids(&self.conn).await?;
+                let (ready, waiting) =
+                    ids.into_iter().partition::<Vec<_>, _>(|id| arrived.contains(id));
 
-    let mut handles = JoinSet::new();
+                if !waiting.is_empty() {
+                    tracing::info!(
+                        "Warmup | {} account(s) are left out until their bridge deposit arrives \
+                         on Eclipse",
+                        waiting.len()
+                    );
+                }
 
-    for batch in accounts_ids.chunks(chunk_size) {
-        let conn = connection.clone();
-        let client = rpc_client.clone();
-        let batch = batch.to_vec();
+                ready
+            }
+            false => ids,
+        };
 
-        spawn_task(&mut handles, batch, conn, client, config.clone());
+        match &self.plan {
+            Some(plan) => {
+                let planned = PlanQuery::get_pending_account_ids(plan.id, &self.conn).await?;
+                Ok(ids.into_iter().filter(|id| planned.contains(id)).collect())
+            }
+            None => Ok(ids),
+        }
     }
 
-    while let Some(res) = handles.join_next().await {
-        let (batch_ids, thread_res) = res.unwrap();
+    async fn execute(
+        &self,
+        account_id: i32,
+        report: Option<&DryRunReport>,
+    ) -> eyre::Result<TaskOutcome> {
+        if let Some(plan) = &self.plan {
+            return match report {
+                Some(report) => {
+                    simulate_planned_action(
+                        &self.conn,
+                        account_id,
+                        &self.rpc_client,
+                        &self.config,
+                        plan,
+                        report,
+                    )
+                    .await?;
+
+                    Ok(TaskOutcome::Done)
+                }
+                None => {
+                    execute_planned_action(
+                        &self.conn,
+                        account_id,
+                        &self.rpc_client,
+                        &self.config,
+                        plan,
+                    )
+                    .await
+                }
+            };
+        }
 
-        if let Err(e) = thread_res {
-            tracing::error!("Thread execution stopped with error: {e}. Restarting the thread...");
-            let client = rpc_client.clone();
-            let conn = connection.clone();
+        if let Some(report) = report {
+            match &self.scenario {
+                Some(scenario) => {
+                    simulate_scenario_step(
+                        &self.conn,
+                        account_id,
+                        &self.rpc_client,
+                        &self.config,
+                        scenario,
+                        report,
+                    )
+                    .await?
+                }
+                None => {
+                    simulate_warmup_actions(
+                        &self.conn,
+                        account_id,
+                        &self.rpc_client,
+                        &self.config,
+                        report,
+                    )
+                    .await?
+                }
+            }
 
-            spawn_task(&mut handles, batch_ids, conn, client, config.clone());
+            return Ok(TaskOutcome::Done);
         }
-    }
 
-    tracing::info!("No more active wallets left!");
+        let account = AccountQuery::find_account_by_id(account_id, &self.conn).await?;
+        if let Some(until) = account.next_action_at.filter(|at| *at > Utc::now()) {
+            return Ok(TaskOutcome::NotDue { until });
+        }
 
-    Ok(())
-}
+        let action_executed = match &self.scenario {
+            Some(scenario) => {
+                execute_scenario_step(
+                    &self.conn,
+                    account_id,
+                    &self.rpc_client,
+                    &self.config,
+                    scenario,
+                )
+                .await?
+            }
+            None => {
+                execute_random_warmup_action(&self.conn, account_id, &self.rpc_client, &self.config)
+                    .await?
+            }
+        };
 
-async fn process_batch<C>(
-    batch: Vec<i32>,
-    conn: C,
-    config: Arc<Config>,
-    rpc_client: Arc<RpcClient>,
-) -> eyre::Result<()>
-where
-    C: ConnectionTrait + Clone,
-{
-    loop {
-        let leftover_ids = AccountQuery::get_active_accounts_ids_by_ids(&batch, &conn).await;
-
-        match leftover_ids {
-            Ok(mut ids) => {
-                ids.shuffle(&mut thread_rng());
-
-                for id in ids {
-                    execute_random_warmup_action(
-                        conn.clone(),
-                        id,
-                        rpc_client.clone(),
-                        config.clone(),
-                    )
+        match action_executed {
+            Some(action) => {
+                schedule_next_action(&self.conn, &account, &self.config, self.scenario.as_ref())
                     .await?;
-                    pretty_sleep(config.lifinity.wallet_sleep_delay_range, false).await;
-                }
-            }
-            Err(_) => {
-                break;
+
+                Ok(TaskOutcome::Continue { sleep_range: Some(action.sleep_range(&self.config)) })
             }
+            None => Ok(TaskOutcome::Done),
         }
     }
 
+    fn sleep_range(&self) -> [u32; 2] {
+        self.config.lifinity.wallet_sleep_delay_range
+    }
+}
+
+pub async fn warmup_mode(connection: DbConn, config: Arc<Config>) -> eyre::Result<()> {
+    let rpc_client = Arc::new(RpcClient::new(config.general.eclipse_rpc_url.to_string()));
+
+    let plan = load_plan(&connection).await?;
+    if let Some(plan) = &plan {
+        tracing::info!("Running the approved plan #{}", plan.id);
+    }
+
+    let scenario = match &config.general.scenario {
+        Some(_) if plan.is_some() => None,
+        Some(path) => {
+            let scenario = Scenario::read_from_file(path).await?;
+            tracing::info!("Running the `{}` scenario from `{path}`", scenario.name);
+            Some(scenario)
+        }
+        None => None,
+    };
+
+    let module =
+        WarmupModule { conn: connection, rpc_client, config: config.clone(), scenario, plan };
+
+    run_module(module, config).await
+}
+
+/// Pushes `next_action_at` of the account forward when `[schedule]` is configured. The spread is
+/// based on the goals of the account, or on the average length of the scenario
+async fn schedule_next_action(
+    conn: &DbConn,
+    account: &AccountModel,
+    config: &Config,
+    scenario: Option<&Scenario>,
+) -> eyre::Result<()> {
+    let total_actions = match scenario {
+        Some(scenario) => scenario.expected_runs(),
+        None => ActionProgressQuery::get_account_progress(account.id, conn)
+            .await?
+            .iter()
+            .map(|progress| progress.target.max(0) as u32)
+            .sum(),
+    };
+
+    let Some(delay) = next_action_delay(&config.schedule, total_actions) else {
+        return Ok(());
+    };
+
+    let next_action_at = Utc::now() + delay;
+    AccountMutation::set_next_action_at(account.id, Some(next_action_at), conn).await?;
+
+    tracing::info!(
+        "{} | Next action is scheduled at {}",
+        account.eclipse_pubkey(),
+        next_action_at.to_rfc3339()
+    );
+
     Ok(())
 }
 
-async fn execute_random_warmup_action<C>(
-    conn: C,
+/// Returns the executed action, `Ok(None)` once the account reached its goal and was marked as
+/// inactive
+async fn execute_random_warmup_action(
+    conn: &DbConn,
     id: i32,
-    rpc_client: Arc<RpcClient>,
-    config: Arc<Config>,
-) -> eyre::Result<()>
-where
-    C: ConnectionTrait,
-{
-    let account = AccountQuery::find_account_by_id(id, &conn).await?;
-    let account_goal = AccountGoalQuery::get_account_goal_by_id(id, &conn).await?;
-
-    let Some(action) = account.get_random_warmup_action(account_goal) else {
-        AccountMutation::mark_as_inactive(id, &conn).await?; // if no action found mark as inactive
+    rpc_client: &RpcClient,
+    config: &Config,
+) -> eyre::Result<Option<&'static dyn WarmupAction>> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let (account_goal, progress) = tokio::try_join!(
+        AccountGoalQuery::get_account_goal_by_id(id, conn),
+        ActionProgressQuery::get_account_progress(id, conn)
+    )?;
+
+    let Some(action) = get_random_warmup_action(&account, &account_goal, &progress, config) else {
+        AccountMutation::mark_as_inactive(id, conn).await?; // if no action found mark as inactive
         tracing::warn!("{} | Account goal reached, marking as inactive", account.eclipse_pubkey());
-        return Ok(());
+        return Ok(None);
     };
 
-    match action {
-        WarmupAction::LifinitySwap => {
-            execute_lifinity_swap(account, rpc_client, conn, config).await
-        }
-        WarmupAction::UnderdogCreate => {
-            execute_underdog_create_nft(account, rpc_client, conn).await
-        }
-    }
+    let ctx = ActionContext { account: &account, rpc_client, config, conn, params: None };
+    action.execute(ctx).await?;
+    ActionProgressMutation::increase_done(id, action.name(), conn).await?;
+
+    Ok(Some(action))
 }
 
-async fn execute_lifinity_swap<C>(
-    account: AccountModel,
-    rpc_client: Arc<RpcClient>,
-    conn: C,
-    config: Arc<Config>,
-) -> eyre::Result<()>
-where
-    C: ConnectionTrait,
-{
-    let keypair = account.eclise_keypair();
-    let (token_in, balance) =
-        get_token_with_largest_balance(&rpc_client, &keypair.pubkey(), None).await?;
-    let token_out = Token::get_lifinity_paired_token(&token_in);
-    let balance = Token::to_amount(&token_in, balance);
-
-    let percentage = random_in_range(config.lifinity.balance_percentage_range);
-    let amount_in = balance * percentage as u64 / 100;
+/// Runs the step of the scenario the account stopped at. Returns the executed action, `Ok(None)`
+/// once the scenario is finished and the account was marked as inactive
+async fn execute_scenario_step(
+    conn: &DbConn,
+    id: i32,
+    rpc_client: &RpcClient,
+    config: &Config,
+    scenario: &Scenario,
+) -> eyre::Result<Option<&'static dyn WarmupAction>> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let progress = ScenarioProgressQuery::get_scenario_progress(id, conn).await?;
+
+    let Some(position) = scenario.advance(id, scenario.position(progress), conn).await? else {
+        AccountMutation::mark_as_inactive(id, conn).await?;
+        tracing::warn!("{} | Scenario finished, marking as inactive", account.eclipse_pubkey());
+        return Ok(None);
+    };
+
+    let (action, step) = scenario.action(position.step).expect("Position to be within the steps");
+
+    tracing::info!(
+        "{} | Scenario step {}/{}: {}",
+        account.eclipse_pubkey(),
+        position.step + 1,
+        scenario.steps.len(),
+        action.name()
+    );
+
+    let ctx =
+        ActionContext { account: &account, rpc_client, config, conn, params: Some(&step.params) };
+    action.execute(ctx).await?;
+    ActionProgressMutation::increase_done(id, action.name(), conn).await?;
+    scenario.complete_run(id, position, conn).await?;
+
+    Ok(Some(action))
+}
+
+/// Runs the next action of the plan once it is due, an account whose next action isn't due yet
+/// waits in the queue
+async fn execute_planned_action(
+    conn: &DbConn,
+    id: i32,
+    rpc_client: &RpcClient,
+    config: &Config,
+    plan: &PlanModel,
+) -> eyre::Result<TaskOutcome> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+
+    let Some(planned) = PlanQuery::get_next_planned_action(plan.id, id, conn).await? else {
+        tracing::info!("{} | Every planned action is done", account.eclipse_pubkey());
+        return Ok(TaskOutcome::Done);
+    };
+
+    if planned.scheduled_at > Utc::now() {
+        return Ok(TaskOutcome::NotDue { until: planned.scheduled_at });
+    }
+
+    let (action, params) = parse_planned_action(&planned)?;
 
     tracing::info!(
-        "{} | Swapping {} {} to {}",
-        keypair.pubkey(),
-        Token::to_ui_amount(&token_in, amount_in),
-        token_in.symbol,
-        token_out.symbol
+        "{} | Planned step {}: {} {}",
+        account.eclipse_pubkey(),
+        planned.step,
+        action.name(),
+        format_params(&params)
     );
-    match swap(&rpc_client, &keypair, &token_in.mint, &token_out.mint, amount_in).await {
-        Ok(_) => {
-            AccountMutation::increase_swap_count(account.id, &conn).await?;
+
+    let ctx = ActionContext { account: &account, rpc_client, config, conn, params: Some(&params) };
+    action.execute(ctx).await?;
+    ActionProgressMutation::increase_done(id, action.name(), conn).await?;
+    PlanMutation::mark_executed(planned.id, conn).await?;
+
+    // the plan rolled the whole rest of the scenario, so the account is done with it as well
+    if let (Some(scenario), Some(steps)) = (&plan.scenario, plan.scenario_steps) {
+        if PlanQuery::get_next_planned_action(plan.id, id, conn).await?.is_none() {
+            ScenarioProgressMutation::set_position(id, scenario, steps as usize, None, conn)
+                .await?;
         }
-        Err(e) => tracing::error!("{} | Swap failed: {e}", keypair.pubkey()),
     }
 
+    Ok(TaskOutcome::Continue { sleep_range: Some(action.sleep_range(config)) })
+}
+
+/// Simulates the next action of the plan, whether it is due or not
+async fn simulate_planned_action(
+    conn: &DbConn,
+    id: i32,
+    rpc_client: &RpcClient,
+    config: &Config,
+    plan: &PlanModel,
+    report: &DryRunReport,
+) -> eyre::Result<()> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let keypair = account.eclise_keypair()?;
+
+    let Some(planned) = PlanQuery::get_next_planned_action(plan.id, id, conn).await? else {
+        tracing::info!("{} | [DRY RUN] Every planned action is done", keypair.pubkey());
+        return Ok(());
+    };
+    let (action, params) = parse_planned_action(&planned)?;
+
+    let ctx = ActionContext { account: &account, rpc_client, config, conn, params: Some(&params) };
+    let (result, logs) = match action.simulate(ctx).await {
+        Ok((details, simulation)) => match simulation.err {
+            Some(e) => (Err(e), simulation.logs),
+            None => (Ok(format!("Planned step {}: {details}", planned.step)), simulation.logs),
+        },
+        Err(e) => (Err(e.to_string()), vec![]),
+    };
+
+    report.record(DryRunEntry {
+        account_id: id,
+        wallet: keypair.pubkey().to_string(),
+        action: action.name(),
+        result,
+        logs,
+    });
+
     Ok(())
 }
 
-async fn execute_underdog_create_nft<C>(
-    account: AccountModel,
-    rpc_client: Arc<RpcClient>,
-    conn: C,
-) -> eyre::Result<()>
-where
-    C: ConnectionTrait,
-{
-    let keypair = account.eclise_keypair();
-    let proxy = account.proxy();
-
-    tracing::info!("{} | Creating an NFT", keypair.pubkey());
-    match create_collection(&rpc_client, &keypair, proxy.as_ref()).await {
-        Ok(_) => {
-            AccountMutation::increase_create_count(account.id, &conn).await?;
-        }
-        Err(e) => tracing::error!("{} | Failed to create an NFT: {e}", keypair.pubkey()),
+fn parse_planned_action(
+    planned: &PlannedActionModel,
+) -> eyre::Result<(&'static dyn WarmupAction, toml::Table)> {
+    let action = find_action(&planned.action_name)
+        .ok_or_else(|| eyre::eyre!("Unknown planned action `{}`", planned.action_name))?;
+    let params = serde_json::from_str::<toml::Table>(&planned.params)?;
+
+    Ok((action, params))
+}
+
+/// Simulates the scenario step the account stopped at, without rolling or saving anything
+async fn simulate_scenario_step(
+    conn: &DbConn,
+    id: i32,
+    rpc_client: &RpcClient,
+    config: &Config,
+    scenario: &Scenario,
+    report: &DryRunReport,
+) -> eyre::Result<()> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let progress = ScenarioProgressQuery::get_scenario_progress(id, conn).await?;
+    let keypair = account.eclise_keypair()?;
+
+    let position = scenario.position(progress);
+    let Some((action, step)) = scenario.action(position.step) else {
+        tracing::info!("{} | [DRY RUN] Scenario finished, nothing to do", keypair.pubkey());
+        return Ok(());
+    };
+
+    let ctx =
+        ActionContext { account: &account, rpc_client, config, conn, params: Some(&step.params) };
+    let (result, logs) = match action.simulate(ctx).await {
+        Ok((details, simulation)) => match simulation.err {
+            Some(e) => (Err(e), simulation.logs),
+            None => (Ok(format!("Step {}: {details}", position.step + 1)), simulation.logs),
+        },
+        Err(e) => (Err(e.to_string()), vec![]),
+    };
+
+    report.record(DryRunEntry {
+        account_id: id,
+        wallet: keypair.pubkey().to_string(),
+        action: action.name(),
+        result,
+        logs,
+    });
+
+    Ok(())
+}
+
+/// Simulates every action the account still needs once, without touching the database
+async fn simulate_warmup_actions(
+    conn: &DbConn,
+    id: i32,
+    rpc_client: &RpcClient,
+    config: &Config,
+    report: &DryRunReport,
+) -> eyre::Result<()> {
+    let account = AccountQuery::find_account_by_id(id, conn).await?;
+    let (account_goal, progress) = tokio::try_join!(
+        AccountGoalQuery::get_account_goal_by_id(id, conn),
+        ActionProgressQuery::get_account_progress(id, conn)
+    )?;
+    let keypair = account.eclise_keypair()?;
+
+    let actions = get_needed_warmup_actions(&account, &account_goal, &progress);
+
+    if actions.is_empty() {
+        tracing::info!("{} | [DRY RUN] Account goal reached, nothing to do", keypair.pubkey());
+        return Ok(());
+    }
+
+    let ctx = ActionContext { account: &account, rpc_client, config, conn, params: None };
+
+    for action in actions {
+        let (result, logs) = match action.simulate(ctx).await {
+            Ok((details, simulation)) => match simulation.err {
+                Some(e) => (Err(e), simulation.logs),
+                None => (Ok(details), simulation.logs),
+            },
+            Err(e) => (Err(e.to_string()), vec![]),
+        };
+
+        report.record(DryRunEntry {
+            account_id: id,
+            wallet: keypair.pubkey().to_string(),
+            action: action.name(),
+            result,
+            logs,
+        });
     }
 
     Ok(())
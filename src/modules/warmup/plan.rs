@@ -0,0 +1,283 @@
+use std::{collections::HashMap, path::Path};
+
+use chrono::{Duration, Utc};
+use sea_orm::{DbConn, DbErr, TransactionTrait};
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    db::{
+        entities::prelude::{AccountGoalModel, AccountModel, ActionProgressModel, PlanModel},
+        service::prelude::*,
+    },
+    modules::schedule::next_action_delay,
+    utils::misc::random_in_range,
+};
+
+use super::{
+    action::{get_random_warmup_action, PlanState, WarmupAction},
+    scenario::Scenario,
+};
+
+pub const PLAN_EXPORT_FILE_PATH: &str = "data/plan.csv";
+
+#[derive(Serialize)]
+struct PlanRecord<'a> {
+    account_id: i32,
+    label: &'a str,
+    eclipse_address: &'a str,
+    step: i32,
+    action: &'a str,
+    params: String,
+    scheduled_at: String,
+    executed_at: String,
+}
+
+/// Plans every action the active accounts matching `general.TAG` have left and stores the plan
+/// for review, replacing the previous one. With `general.SCENARIO` set the rest of the scenario is
+/// rolled, otherwise the count goals are spread by the action weights
+pub async fn generate_plan(config: &Config, conn: &DbConn) -> eyre::Result<()> {
+    let scenario = match &config.general.scenario {
+        Some(path) => Some(Scenario::read_from_file(path).await?),
+        None => None,
+    };
+
+    let accounts =
+        match AccountQuery::get_active_accounts(config.general.tag.as_deref(), conn).await {
+            Ok(accounts) => accounts,
+            Err(DbErr::RecordNotFound(_)) => {
+                tracing::info!("No active accounts to plan for");
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+    let goals = AccountGoalQuery::get_all_account_goals(conn)
+        .await?
+        .into_iter()
+        .map(|goal| (goal.id, goal))
+        .collect::<HashMap<_, _>>();
+
+    let mut progress = HashMap::<_, Vec<_>>::new();
+    for row in ActionProgressQuery::get_all_progress(conn).await? {
+        progress.entry(row.account_id).or_default().push(row);
+    }
+
+    let now = Utc::now();
+    let mut steps = vec![];
+    let mut planned_accounts = 0;
+
+    for account in &accounts {
+        let actions = match &scenario {
+            Some(scenario) => {
+                let position = scenario.position(
+                    ScenarioProgressQuery::get_scenario_progress(account.id, conn).await?,
+                );
+
+                scenario
+                    .expand(position)
+                    .into_iter()
+                    .map(|(action, step)| (action, step.params.clone()))
+                    .collect()
+            }
+            None => {
+                let Some(goal) = goals.get(&account.id) else {
+                    tracing::warn!("Account #{} has no goal, skipping", account.id);
+                    continue;
+                };
+                let progress = progress.remove(&account.id).unwrap_or_default();
+
+                plan_goal_actions(account, goal, &progress, config)
+                    .into_iter()
+                    .map(|action| (action, toml::Table::new()))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        if actions.is_empty() {
+            continue;
+        }
+        planned_accounts += 1;
+
+        let mut state = PlanState::default();
+        let mut scheduled_at = account.next_action_at.map_or(now, |at| at.max(now));
+        let total_actions = actions.len() as u32;
+
+        for (index, (action, params)) in actions.into_iter().enumerate() {
+            let params = action
+                .plan_params(config, &params, &mut state)
+                .map_err(|e| eyre::eyre!("Account #{}: {e}", account.id))?;
+
+            steps.push(PlannedStep {
+                account_id: account.id,
+                step: index as i32 + 1,
+                action_name: action.name().to_string(),
+                params: serde_json::to_string(&params)?,
+                scheduled_at,
+            });
+
+            let sleep = Duration::seconds(random_in_range(action.sleep_range(config)) as i64);
+            let delay = next_action_delay(&config.schedule, total_actions).unwrap_or_default();
+
+            scheduled_at += sleep.max(delay);
+        }
+    }
+
+    if steps.is_empty() {
+        tracing::info!("Nothing left to plan for any wallet");
+        return Ok(());
+    }
+
+    let actions = steps.len();
+
+    let txn = conn.begin().await?;
+    let scenario = scenario.as_ref().map(|scenario| (scenario.name.as_str(), scenario.steps.len()));
+    let plan = PlanMutation::create_plan(steps, scenario, &txn).await?;
+    txn.commit().await?;
+
+    tracing::info!(
+        "Plan #{} created: {actions} action(s) for {planned_accounts} account(s). Review it with \
+         `plan export` and let the warmup run it with `plan approve`",
+        plan.id
+    );
+
+    Ok(())
+}
+
+/// Picks the actions needed to reach the count goals with the same weighted rolls the warmup makes.
+/// The volume goal is left out, the USD value of a swap is only known once it runs
+fn plan_goal_actions(
+    account: &AccountModel,
+    goal: &AccountGoalModel,
+    progress: &[ActionProgressModel],
+    config: &Config,
+) -> Vec<&'static dyn WarmupAction> {
+    let goal = AccountGoalModel { volume_usd: None, ..goal.clone() };
+    let mut progress = progress.to_vec();
+    let mut actions = vec![];
+
+    while let Some(action) = get_random_warmup_action(account, &goal, &progress, config) {
+        let Some(row) = progress.iter_mut().find(|row| row.action_name == action.name()) else {
+            break;
+        };
+
+        row.done += 1;
+        actions.push(action);
+    }
+
+    actions
+}
+
+/// Writes every action of the plan to a CSV file
+pub async fn export_plan(path: impl AsRef<Path>, conn: &DbConn) -> eyre::Result<()> {
+    let path = path.as_ref();
+    let plan = get_plan(conn).await?;
+
+    let accounts = AccountQuery::get_all_accounts(conn)
+        .await?
+        .into_iter()
+        .map(|account| (account.id, account))
+        .collect::<HashMap<_, _>>();
+    let actions = PlanQuery::get_planned_actions(plan.id, conn).await?;
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    for action in &actions {
+        let account = accounts.get(&action.account_id);
+        let params = serde_json::from_str::<toml::Table>(&action.params)?;
+
+        writer.serialize(PlanRecord {
+            account_id: action.account_id,
+            label: account.and_then(|account| account.label.as_deref()).unwrap_or_default(),
+            eclipse_address: account
+                .map(|account| account.eclipse_address.as_str())
+                .unwrap_or_default(),
+            step: action.step,
+            action: &action.action_name,
+            params: format_params(&params),
+            scheduled_at: action.scheduled_at.to_rfc3339(),
+            executed_at: action.executed_at.map(|at| at.to_rfc3339()).unwrap_or_default(),
+        })?;
+    }
+
+    let contents = writer.into_inner().map_err(|e| eyre::eyre!("Failed to write the plan: {e}"))?;
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(|e| eyre::eyre!("Failed to write `{}`: {e}", path.display()))?;
+
+    let executed = actions.iter().filter(|action| action.executed_at.is_some()).count();
+    let approval = match plan.approved_at {
+        Some(approved_at) => format!("approved at {}", approved_at.to_rfc3339()),
+        None => "waiting for approval".to_string(),
+    };
+
+    tracing::info!(
+        "Plan #{} ({approval}): {} action(s), {executed} executed, exported to `{}`",
+        plan.id,
+        actions.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+pub async fn approve_plan(conn: &DbConn) -> eyre::Result<()> {
+    let plan = get_plan(conn).await?;
+
+    if plan.approved_at.is_some() {
+        tracing::info!("Plan #{} is already approved", plan.id);
+        return Ok(());
+    }
+
+    let plan = PlanMutation::approve_plan(plan, conn).await?;
+    tracing::info!("Plan #{} approved, the warmup will run it", plan.id);
+
+    Ok(())
+}
+
+pub async fn discard_plan(conn: &DbConn) -> eyre::Result<()> {
+    PlanMutation::delete_plans(conn).await?;
+    tracing::info!("Plan discarded, the warmup decides every action on the fly again");
+
+    Ok(())
+}
+
+/// Plan the warmup has to run, `None` when there is no plan or every action of it was executed.
+/// Fails while the plan is waiting for approval, so nothing runs that wasn't signed off
+pub async fn load_plan(conn: &DbConn) -> eyre::Result<Option<PlanModel>> {
+    let Some(plan) = PlanQuery::get_plan(conn).await? else {
+        return Ok(None);
+    };
+
+    if PlanQuery::get_pending_account_ids(plan.id, conn).await?.is_empty() {
+        return Ok(None);
+    }
+
+    if plan.approved_at.is_none() {
+        eyre::bail!(
+            "Plan #{} is waiting for approval. Review it with `plan export`, then run `plan \
+             approve` or drop it with `plan discard`",
+            plan.id
+        );
+    }
+
+    Ok(Some(plan))
+}
+
+async fn get_plan(conn: &DbConn) -> eyre::Result<PlanModel> {
+    PlanQuery::get_plan(conn)
+        .await?
+        .ok_or_else(|| eyre::eyre!("No plan found, create one with `plan generate`"))
+}
+
+/// `key=value` pairs separated by spaces, strings are written without quotes
+pub fn format_params(params: &toml::Table) -> String {
+    params
+        .iter()
+        .map(|(key, value)| match value {
+            toml::Value::String(value) => format!("{key}={value}"),
+            value => format!("{key}={value}"),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
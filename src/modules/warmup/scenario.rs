@@ -0,0 +1,207 @@
+use std::path::Path;
+
+use rand::{thread_rng, Rng};
+use sea_orm::ConnectionTrait;
+use serde::Deserialize;
+
+use crate::{
+    db::{entities::prelude::ScenarioProgressModel, service::prelude::ScenarioProgressMutation},
+    utils::misc::random_in_range,
+};
+
+use super::action::{find_action, WarmupAction};
+
+/// Ordered list of steps every account goes through, loaded from a TOML file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Stored with the position of every account, renaming the scenario starts it over
+    pub name: String,
+    pub steps: Vec<ScenarioStep>,
+}
+
+#[derive(Deserialize)]
+pub struct ScenarioStep {
+    pub action: String,
+    /// How many times the step runs in a row, rolled once per account
+    #[serde(default = "default_repeat")]
+    pub repeat: [u32; 2],
+    /// Chance of the step being run at all, rolled once per account
+    #[serde(default = "default_probability")]
+    pub probability: f64,
+    /// Everything else is passed to the action
+    #[serde(flatten)]
+    pub params: toml::Table,
+}
+
+/// Position of an account in the scenario
+pub struct Position {
+    pub step: usize,
+    /// `None` until the repeat count of the step is rolled
+    pub runs_left: Option<u32>,
+}
+
+fn default_repeat() -> [u32; 2] {
+    [1, 1]
+}
+
+fn default_probability() -> f64 {
+    1.0
+}
+
+impl Scenario {
+    pub async fn read_from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to read `{}`: {e}", path.display()))?;
+
+        let scenario = toml::from_str::<Scenario>(&contents)
+            .map_err(|e| eyre::eyre!("Failed to parse `{}`: {e}", path.display()))?;
+
+        scenario
+            .validate()
+            .map_err(|e| eyre::eyre!("Invalid scenario `{}`: {e}", path.display()))?;
+
+        Ok(scenario)
+    }
+
+    fn validate(&self) -> eyre::Result<()> {
+        if self.steps.is_empty() {
+            eyre::bail!("the scenario has no steps");
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let number = index + 1;
+
+            let action = find_action(&step.action)
+                .ok_or_else(|| eyre::eyre!("step {number}: unknown action `{}`", step.action))?;
+
+            if !(0.0..=1.0).contains(&step.probability) {
+                eyre::bail!("step {number}: probability must be between 0 and 1");
+            }
+
+            action.check_params(&step.params).map_err(|e| eyre::eyre!("step {number}: {e}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Saved position of the account, the scenario starts over when the account ran another one
+    pub fn position(&self, progress: Option<ScenarioProgressModel>) -> Position {
+        match progress {
+            Some(progress) if progress.scenario == self.name => Position {
+                step: progress.step as usize,
+                runs_left: progress.runs_left.map(|runs| runs as u32),
+            },
+            _ => Position { step: 0, runs_left: None },
+        }
+    }
+
+    pub fn action(&self, step: usize) -> Option<(&'static dyn WarmupAction, &ScenarioStep)> {
+        let step = self.steps.get(step)?;
+        let action = find_action(&step.action).expect("Actions to be checked on load");
+
+        Some((action, step))
+    }
+
+    /// Average number of runs in the whole scenario, used to spread it over time
+    pub fn expected_runs(&self) -> u32 {
+        self.steps
+            .iter()
+            .map(|step| step.probability * (step.repeat[0] + step.repeat[1]) as f64 / 2.0)
+            .sum::<f64>()
+            .ceil() as u32
+    }
+
+    /// Rolls the rest of the scenario from `position` without saving anything, returns every run
+    /// in order
+    pub fn expand(&self, position: Position) -> Vec<(&'static dyn WarmupAction, &ScenarioStep)> {
+        let mut runs = vec![];
+
+        for index in position.step..self.steps.len() {
+            let (action, step) = self.action(index).expect("Index to be within the steps");
+
+            let count = match position.runs_left {
+                Some(runs_left) if index == position.step => runs_left,
+                _ if thread_rng().gen_bool(step.probability) => random_in_range(step.repeat),
+                _ => 0,
+            };
+
+            runs.extend((0..count).map(|_| (action, step)));
+        }
+
+        runs
+    }
+
+    /// Moves the account to the next step that has to be run, rolling the probability and the
+    /// repeat count of every step it enters. Every roll is saved, so an interrupted run resumes
+    /// with the same decisions. Returns `None` once the scenario is finished
+    pub async fn advance<C>(
+        &self,
+        account_id: i32,
+        mut position: Position,
+        conn: &C,
+    ) -> eyre::Result<Option<Position>>
+    where
+        C: ConnectionTrait,
+    {
+        loop {
+            let Some(step) = self.steps.get(position.step) else {
+                return Ok(None);
+            };
+
+            let runs_left = match position.runs_left {
+                Some(runs_left) => runs_left,
+                None if thread_rng().gen_bool(step.probability) => random_in_range(step.repeat),
+                None => 0,
+            };
+
+            if runs_left > 0 {
+                ScenarioProgressMutation::set_position(
+                    account_id,
+                    &self.name,
+                    position.step,
+                    Some(runs_left),
+                    conn,
+                )
+                .await?;
+
+                return Ok(Some(Position { step: position.step, runs_left: Some(runs_left) }));
+            }
+
+            position = Position { step: position.step + 1, runs_left: None };
+            ScenarioProgressMutation::set_position(
+                account_id,
+                &self.name,
+                position.step,
+                None,
+                conn,
+            )
+            .await?;
+        }
+    }
+
+    /// Records a finished run of the current step
+    pub async fn complete_run<C>(
+        &self,
+        account_id: i32,
+        position: Position,
+        conn: &C,
+    ) -> eyre::Result<()>
+    where
+        C: ConnectionTrait,
+    {
+        let runs_left = position.runs_left.unwrap_or_default().saturating_sub(1);
+
+        let (step, runs_left) = match runs_left {
+            0 => (position.step + 1, None),
+            runs_left => (position.step, Some(runs_left)),
+        };
+
+        ScenarioProgressMutation::set_position(account_id, &self.name, step, runs_left, conn)
+            .await?;
+
+        Ok(())
+    }
+}
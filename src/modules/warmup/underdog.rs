@@ -0,0 +1,90 @@
+use solana_sdk::signer::Signer;
+
+use crate::{
+    config::Config,
+    db::{
+        entities::sea_orm_active_enums::{Chain, TxAction, TxStatus},
+        service::prelude::TxRecord,
+    },
+    onchain::eclipse::{
+        common::tx::{get_tx_details, TxSimulation},
+        underdog::create::{create_collection, simulate_create_collection},
+    },
+};
+
+use super::action::{record_failed_tx, record_tx, ActionContext, ActionFuture, WarmupAction};
+
+pub struct UnderdogCreate;
+
+impl WarmupAction for UnderdogCreate {
+    fn name(&self) -> &'static str {
+        "underdog_create"
+    }
+
+    fn config_section(&self) -> &'static str {
+        "underdog"
+    }
+
+    fn target_range(&self, config: &Config) -> [u32; 2] {
+        config.underdog.create_count_range
+    }
+
+    fn weight(&self, config: &Config) -> u32 {
+        config.underdog.weight
+    }
+
+    fn sleep_range(&self, config: &Config) -> [u32; 2] {
+        config.underdog.wallet_sleep_delay_range.unwrap_or(config.lifinity.wallet_sleep_delay_range)
+    }
+
+    fn execute<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, ()> {
+        Box::pin(execute_underdog_create_nft(ctx))
+    }
+
+    fn simulate<'a>(&'a self, ctx: ActionContext<'a>) -> ActionFuture<'a, (String, TxSimulation)> {
+        Box::pin(simulate_underdog_create_nft(ctx))
+    }
+}
+
+async fn simulate_underdog_create_nft(
+    ctx: ActionContext<'_>,
+) -> eyre::Result<(String, TxSimulation)> {
+    let ActionContext { account, rpc_client, .. } = ctx;
+
+    let keypair = account.eclise_keypair()?;
+    let proxy = account.proxy();
+
+    let simulation = simulate_create_collection(rpc_client, &keypair, proxy.as_ref()).await?;
+
+    let details = format!(
+        "Would create an NFT collection, compute units: {}",
+        simulation.units_consumed.unwrap_or_default()
+    );
+
+    Ok((details, simulation))
+}
+
+async fn execute_underdog_create_nft(ctx: ActionContext<'_>) -> eyre::Result<()> {
+    let ActionContext { account, rpc_client, conn, .. } = ctx;
+
+    let keypair = account.eclise_keypair()?;
+    let proxy = account.proxy();
+
+    tracing::info!("{} | Creating an NFT", keypair.pubkey());
+    let record = TxRecord::new(account.id, Chain::Eclipse, TxAction::UnderdogCreate);
+
+    match create_collection(rpc_client, &keypair, proxy.as_ref()).await {
+        Ok(signature) => {
+            let details = get_tx_details(rpc_client, &signature).await;
+            let record = record.tx_hash(signature).fee(details.fee);
+
+            record_tx(record, TxStatus::Success, conn).await;
+        }
+        Err(e) => {
+            record_failed_tx(record, &e, conn).await;
+            eyre::bail!("Failed to create an NFT: {e}")
+        }
+    }
+
+    Ok(())
+}
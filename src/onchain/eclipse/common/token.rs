@@ -41,6 +41,13 @@ impl Token {
         }
     }
 
+    pub fn from_symbol(symbol: &str) -> eyre::Result<Self> {
+        [Token::ETH, Token::SOL, Token::USDC]
+            .into_iter()
+            .find(|token| token.symbol.eq_ignore_ascii_case(symbol))
+            .ok_or_else(|| eyre::eyre!("Unknown token `{symbol}`, expected ETH, SOL or USDC"))
+    }
+
     pub fn get_lifinity_paired_token(token_in: &Token) -> Token {
         match *token_in {
             Token::ETH | Token::SOL => Token::USDC,
@@ -1,11 +1,52 @@
+use std::fmt::{self, Display, Formatter};
+
 use super::constants::ECLIPSE_EXPLORER_URL;
-use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::SerializableTransaction,
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature};
+use solana_transaction_status::UiTransactionEncoding;
+
+pub struct TxSimulation {
+    pub err: Option<String>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct TxDetails {
+    /// Fee paid in lamports
+    pub fee: Option<u64>,
+    pub logs: Vec<String>,
+}
+
+/// A transaction that was sent but not confirmed. It travels inside the `eyre::Report`, so the
+/// failure can still be recorded with its signature
+#[derive(Debug)]
+pub struct SendTxError {
+    pub signature: Signature,
+    message: String,
+}
+
+impl Display for SendTxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to send tx {}: {}", self.signature, self.message)
+    }
+}
+
+impl std::error::Error for SendTxError {}
+
+/// Signature of the transaction the error was raised for, `None` when it failed before sending
+pub fn failed_tx_signature(error: &eyre::Report) -> Option<Signature> {
+    error.downcast_ref::<SendTxError>().map(|error| error.signature)
+}
 
 pub async fn send_and_confirm_tx(
     provider: &RpcClient,
     tx: impl SerializableTransaction,
-) -> eyre::Result<()> {
-    let tx_signature = tx.get_signature();
+) -> eyre::Result<Signature> {
+    let tx_signature = *tx.get_signature();
 
     tracing::info!("Sending transaction: {}{}", ECLIPSE_EXPLORER_URL, tx_signature);
 
@@ -14,9 +55,46 @@ pub async fn send_and_confirm_tx(
             tracing::info!("Transaction confirmed");
         }
         Err(e) => {
-            return Err(eyre::eyre!("Failed to send tx: {e}"));
+            return Err(SendTxError { signature: tx_signature, message: e.to_string() }.into());
+        }
+    }
+
+    Ok(tx_signature)
+}
+
+/// Fetches the fee and logs of a confirmed transaction. Failures are only logged since the
+/// transaction itself already went through
+pub async fn get_tx_details(provider: &RpcClient, signature: &Signature) -> TxDetails {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+
+    match provider.get_transaction_with_config(signature, config).await {
+        Ok(tx) => match tx.transaction.meta {
+            Some(meta) => TxDetails {
+                fee: Some(meta.fee),
+                logs: Option::from(meta.log_messages).unwrap_or_default(),
+            },
+            None => TxDetails::default(),
+        },
+        Err(e) => {
+            tracing::warn!("Failed to fetch details of {signature}: {e}");
+            TxDetails::default()
         }
     }
+}
+
+pub async fn simulate_tx(
+    provider: &RpcClient,
+    tx: impl SerializableTransaction,
+) -> eyre::Result<TxSimulation> {
+    let simulation = provider.simulate_transaction(&tx).await?.value;
 
-    Ok(())
+    Ok(TxSimulation {
+        err: simulation.err.map(|e| e.to_string()),
+        logs: simulation.logs.unwrap_or_default(),
+        units_consumed: simulation.units_consumed,
+    })
 }
@@ -16,8 +16,35 @@ pub async fn get_token_with_largest_balance(
     owner: &Pubkey,
     proxy: Option<&Proxy>,
 ) -> eyre::Result<(Token, f64)> {
-    let token_to_price_mapping = get_tickers_usd_value(proxy).await?;
+    let (token_to_price_mapping, token_to_amount_mapping) =
+        tokio::try_join!(get_tickers_usd_value(proxy), get_token_balances(provider, owner))?;
 
+    let max_value_token = token_to_amount_mapping
+        .iter()
+        .map(|(token, amount)| (token, amount * token_to_price_mapping.get(token).unwrap()))
+        .max_by(|(_, val_a), (_, val_b)| val_a.partial_cmp(val_b).unwrap_or(Ordering::Equal))
+        .map(|(token, _)| *token)
+        .ok_or_else(|| eyre::eyre!("No token with a max value found"))?;
+
+    Ok((max_value_token, *token_to_amount_mapping.get(&max_value_token).unwrap()))
+}
+
+/// Balance of `token` in UI units, zero when the wallet has no account for it
+pub async fn get_token_balance(
+    provider: &RpcClient,
+    owner: &Pubkey,
+    token: &Token,
+) -> eyre::Result<f64> {
+    let balances = get_token_balances(provider, owner).await?;
+
+    Ok(balances.get(token).copied().unwrap_or_default())
+}
+
+/// Native balance and balances of the known SPL tokens in UI units
+async fn get_token_balances(
+    provider: &RpcClient,
+    owner: &Pubkey,
+) -> eyre::Result<HashMap<Token, f64>> {
     let (native_balance, spl_tokens) = tokio::try_join!(
         provider.get_balance(owner),
         provider.get_token_accounts_by_owner(
@@ -26,13 +53,9 @@ pub async fn get_token_with_largest_balance(
         )
     )?;
 
-    let mut token_to_usd_value_mapping = HashMap::new();
     let mut token_to_amount_mapping = HashMap::new();
 
     let native_amount = native_balance as f64 / 10f64.powi(Token::ETH.decimals as i32);
-    let native_usd_value = native_amount * token_to_price_mapping.get(&Token::ETH).unwrap();
-
-    token_to_usd_value_mapping.insert(Token::ETH, native_usd_value);
     token_to_amount_mapping.insert(Token::ETH, native_amount);
 
     for token in spl_tokens {
@@ -46,18 +69,9 @@ pub async fn get_token_with_largest_balance(
             };
 
             let base_amount = amount / 10f64.powi(token.decimals as i32);
-            let usd_value = base_amount * token_to_price_mapping.get(&token).unwrap();
-
-            token_to_usd_value_mapping.insert(token, usd_value);
             token_to_amount_mapping.insert(token, base_amount);
         }
     }
 
-    let max_value_token = token_to_usd_value_mapping
-        .iter()
-        .max_by(|(_, val_a), (_, val_b)| val_a.partial_cmp(val_b).unwrap_or(Ordering::Equal))
-        .map(|(token, _)| *token)
-        .ok_or_else(|| eyre::eyre!("No token with a max value found"))?;
-
-    Ok((max_value_token, *token_to_amount_mapping.get(&max_value_token).unwrap()))
+    Ok(token_to_amount_mapping)
 }
@@ -2,4 +2,4 @@ mod constants;
 mod math;
 pub mod swap;
 mod typedefs;
-mod utils;
+pub mod utils;
@@ -1,12 +1,13 @@
 use borsh::BorshDeserialize;
 use eyre::bail;
-use solana_client::{
-    nonblocking::rpc_client::RpcClient,
-    rpc_response::{Response, RpcSimulateTransactionResult},
-};
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
-    commitment_config::CommitmentConfig, instruction::Instruction, pubkey::Pubkey,
-    signature::Keypair, signer::Signer, transaction::Transaction,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
 };
 
 use crate::onchain::eclipse::{
@@ -15,7 +16,7 @@ use crate::onchain::eclipse::{
         derive::{derive_ata, derive_program_authority},
         ixs::{create_ata, sync_native, unwrap_eth},
         state::{Account, Amm},
-        tx::send_and_confirm_tx,
+        tx::{send_and_confirm_tx, simulate_tx, TxSimulation},
         typedefs::CreateAtaArgs,
     },
     lifinity::utils::extract_out_value,
@@ -150,33 +151,26 @@ pub async fn prepare_swap(
     Ok(ixs)
 }
 
-async fn simulate_transaction(
-    provider: &RpcClient,
-    wallet_kp: &Keypair,
-    swap_info: SwapInfo,
-) -> eyre::Result<Response<RpcSimulateTransactionResult>> {
-    let swap_ixs = prepare_swap(provider, swap_info).await?;
-
-    let (recent_blockhash, _) =
-        provider.get_latest_blockhash_with_commitment(CommitmentConfig::finalized()).await?;
-
-    let tx = Transaction::new_signed_with_payer(
-        &swap_ixs,
-        Some(&wallet_kp.pubkey()),
-        &[wallet_kp],
-        recent_blockhash,
-    );
+pub struct SwapSimulation {
+    pub amount_out: u64,
+    pub simulation: TxSimulation,
+}
 
-    let simulation = provider.simulate_transaction(&tx).await?;
+pub struct SwapReceipt {
+    pub signature: Signature,
+    /// Amount out expected by the simulation the swap was built from
+    pub expected_amount_out: u64,
+}
 
-    Ok(simulation)
+pub fn amount_out_from_logs(logs: &[String]) -> Option<u64> {
+    logs.iter().filter_map(|log| extract_out_value(log)).next()
 }
 
-async fn execute_transaction(
+async fn build_transaction(
     provider: &RpcClient,
     wallet_kp: &Keypair,
     swap_info: SwapInfo,
-) -> eyre::Result<()> {
+) -> eyre::Result<Transaction> {
     let swap_ixs = prepare_swap(provider, swap_info).await?;
 
     let (recent_blockhash, _) =
@@ -189,16 +183,16 @@ async fn execute_transaction(
         recent_blockhash,
     );
 
-    send_and_confirm_tx(provider, tx).await
+    Ok(tx)
 }
 
-pub async fn swap(
-    provider: &RpcClient,
+fn build_swap_info(
     wallet_kp: &Keypair,
     token_a: &Pubkey,
     token_b: &Pubkey,
     amount_in: u64,
-) -> eyre::Result<()> {
+    amount_out: Option<u64>,
+) -> eyre::Result<SwapInfo> {
     let amm_pool_key = match determine_pool_pubkey(token_a, token_b) {
         Some(pubkey) => pubkey,
         None => eyre::bail!("Failed to determine pool pubkey"),
@@ -206,33 +200,48 @@ pub async fn swap(
 
     let should_transfer_source = *token_a == ETH_PUBKEY;
 
-    let simulate_swap_info = SwapInfo::new(
+    Ok(SwapInfo::new(
         &wallet_kp.pubkey(),
         token_a,
         token_b,
         &amm_pool_key,
         amount_in,
-        None,
+        amount_out,
         should_transfer_source,
-    );
+    ))
+}
 
-    let simulation = simulate_transaction(provider, wallet_kp, simulate_swap_info).await?;
+pub async fn simulate_swap(
+    provider: &RpcClient,
+    wallet_kp: &Keypair,
+    token_a: &Pubkey,
+    token_b: &Pubkey,
+    amount_in: u64,
+) -> eyre::Result<SwapSimulation> {
+    let swap_info = build_swap_info(wallet_kp, token_a, token_b, amount_in, None)?;
+    let tx = build_transaction(provider, wallet_kp, swap_info).await?;
 
-    let logs = simulation.value.logs.unwrap();
+    let simulation = simulate_tx(provider, tx).await?;
 
-    let amount_out = logs.iter().filter_map(|log| extract_out_value(log)).next().unwrap_or(0);
+    let amount_out = amount_out_from_logs(&simulation.logs).unwrap_or(0);
 
-    let swap_info = SwapInfo::new(
-        &wallet_kp.pubkey(),
-        token_a,
-        token_b,
-        &amm_pool_key,
-        amount_in,
-        Some(amount_out),
-        should_transfer_source,
-    );
+    Ok(SwapSimulation { amount_out, simulation })
+}
+
+pub async fn swap(
+    provider: &RpcClient,
+    wallet_kp: &Keypair,
+    token_a: &Pubkey,
+    token_b: &Pubkey,
+    amount_in: u64,
+) -> eyre::Result<SwapReceipt> {
+    let SwapSimulation { amount_out, .. } =
+        simulate_swap(provider, wallet_kp, token_a, token_b, amount_in).await?;
+
+    let swap_info = build_swap_info(wallet_kp, token_a, token_b, amount_in, Some(amount_out))?;
+    let tx = build_transaction(provider, wallet_kp, swap_info).await?;
 
-    execute_transaction(provider, wallet_kp, swap_info).await?;
+    let signature = send_and_confirm_tx(provider, tx).await?;
 
-    Ok(())
+    Ok(SwapReceipt { signature, expected_amount_out: amount_out })
 }
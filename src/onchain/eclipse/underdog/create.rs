@@ -15,7 +15,7 @@ use solana_sdk::{
     transaction::VersionedTransaction,
 };
 
-use crate::onchain::eclipse::common::tx::send_and_confirm_tx;
+use crate::onchain::eclipse::common::tx::{send_and_confirm_tx, simulate_tx, TxSimulation};
 
 use super::{api::get_create_nft_tx, schema::CollectionsBody};
 
@@ -26,11 +26,10 @@ fn generate_random_url() -> String {
     format!("https://{domain}.{tld}")
 }
 
-pub async fn create_collection(
-    provider: &RpcClient,
+async fn build_collection_tx(
     wallet: &Keypair,
     proxy: Option<&Proxy>,
-) -> eyre::Result<()> {
+) -> eyre::Result<VersionedTransaction> {
     let account = wallet.pubkey().to_string();
 
     let name: String = Word().fake();
@@ -63,7 +62,25 @@ pub async fn create_collection(
     let new_signature: Signature = wallet.sign_message(&tx.message.serialize());
     tx.signatures[0] = new_signature;
 
-    send_and_confirm_tx(provider, tx).await?;
+    Ok(tx)
+}
+
+pub async fn create_collection(
+    provider: &RpcClient,
+    wallet: &Keypair,
+    proxy: Option<&Proxy>,
+) -> eyre::Result<Signature> {
+    let tx = build_collection_tx(wallet, proxy).await?;
+
+    send_and_confirm_tx(provider, tx).await
+}
+
+pub async fn simulate_create_collection(
+    provider: &RpcClient,
+    wallet: &Keypair,
+    proxy: Option<&Proxy>,
+) -> eyre::Result<TxSimulation> {
+    let tx = build_collection_tx(wallet, proxy).await?;
 
-    Ok(())
+    simulate_tx(provider, tx).await
 }
@@ -1,11 +1,17 @@
-use std::{marker::PhantomData, str::FromStr, sync::Arc};
+use std::{marker::PhantomData, str::FromStr, sync::Arc, time::Duration};
 
 use super::types::Token;
+use crate::utils::secret::Secret;
 use alloy::{
+    consensus::TxEnvelope,
+    eips::BlockNumberOrTag,
     network::{Ethereum, EthereumWallet, TransactionBuilder},
-    primitives::{Address, Bytes, U256},
-    providers::Provider,
-    rpc::types::TransactionRequest,
+    primitives::{utils::format_units, Address, Bytes, TxHash, U256},
+    providers::{
+        utils::Eip1559Estimation, PendingTransactionBuilder, PendingTransactionError, Provider,
+        WatchTxError,
+    },
+    rpc::types::{BlockTransactionsKind, TransactionRequest},
     signers::local::PrivateKeySigner,
     sol,
     transports::Transport,
@@ -20,6 +26,83 @@ sol! {
     }
 }
 
+pub struct SentTransaction {
+    pub hash: TxHash,
+    pub success: bool,
+    /// Fee paid in wei
+    pub fee: U256,
+}
+
+/// Transaction signed and ready to be broadcast
+pub struct SignedTransaction {
+    pub hash: TxHash,
+    pub nonce: u64,
+    envelope: TxEnvelope,
+}
+
+pub struct TransactionSimulation {
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub output: Bytes,
+}
+
+impl TransactionSimulation {
+    pub fn max_fee(&self) -> U256 {
+        U256::from(self.gas_limit) * U256::from(self.max_fee_per_gas)
+    }
+}
+
+/// Fees of the latest block in wei
+pub struct NetworkFees {
+    pub base_fee: u128,
+    pub priority_fee: u128,
+}
+
+/// Highest fees in wei a transaction is sent with, unset limits are not enforced
+#[derive(Debug, Clone, Copy)]
+pub struct FeeCeiling {
+    pub max_base_fee: Option<u128>,
+    pub max_priority_fee: Option<u128>,
+}
+
+impl FeeCeiling {
+    /// Why the network is too expensive right now, `None` when the fees are within the ceiling
+    pub fn exceeded_by(&self, fees: &NetworkFees) -> eyre::Result<Option<String>> {
+        let checks = [
+            ("base fee", fees.base_fee, self.max_base_fee),
+            ("priority fee", fees.priority_fee, self.max_priority_fee),
+        ];
+
+        for (name, fee, max_fee) in checks {
+            if let Some(max_fee) = max_fee.filter(|max_fee| fee > *max_fee) {
+                return Ok(Some(format!(
+                    "The {name} of {} gwei is above the ceiling of {} gwei",
+                    format_units(fee, "gwei")?,
+                    format_units(max_fee, "gwei")?
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Caps the estimated fees, so a spike after the check can't make the transaction pricier. A
+    /// capped transaction may not be mined until the fees drop under the ceiling again, it can stay
+    /// pending for a long time
+    fn apply(&self, estimation: Eip1559Estimation) -> Eip1559Estimation {
+        let max_priority_fee_per_gas = match self.max_priority_fee {
+            Some(max_fee) => estimation.max_priority_fee_per_gas.min(max_fee),
+            None => estimation.max_priority_fee_per_gas,
+        };
+        let max_fee_per_gas = match self.max_base_fee {
+            Some(max_fee) => estimation.max_fee_per_gas.min(max_fee + max_priority_fee_per_gas),
+            None => estimation.max_fee_per_gas,
+        };
+
+        Eip1559Estimation { max_fee_per_gas, max_priority_fee_per_gas }
+    }
+}
+
 pub struct EvmClient<P, T>
 where
     P: Provider<T, Ethereum>,
@@ -29,6 +112,7 @@ where
     chain: NamedChain,
     signer: PrivateKeySigner,
     wallet: EthereumWallet,
+    fee_ceiling: Option<FeeCeiling>,
     _marker: PhantomData<T>,
 }
 
@@ -37,24 +121,60 @@ where
     P: Provider<T, Ethereum>,
     T: Transport + Clone,
 {
-    pub fn new(provider: Arc<P>, private_key: &str, chain: NamedChain) -> Self {
-        let signer = PrivateKeySigner::from_str(private_key).expect("Private key to be valid");
+    pub fn new(provider: Arc<P>, private_key: &Secret, chain: NamedChain) -> Self {
+        let signer =
+            PrivateKeySigner::from_str(private_key.expose()).expect("Private key to be valid");
         let wallet = EthereumWallet::new(signer.clone());
 
-        Self { provider, wallet, signer, chain, _marker: PhantomData }
+        Self { provider, wallet, signer, chain, fee_ceiling: None, _marker: PhantomData }
+    }
+
+    pub fn with_fee_ceiling(mut self, fee_ceiling: Option<FeeCeiling>) -> Self {
+        self.fee_ceiling = fee_ceiling;
+        self
     }
 
     pub fn address(&self) -> Address {
         self.signer.address()
     }
 
-    pub async fn send_transaction(
+    /// Base fee of the latest block and the priority fee a transaction would be sent with
+    pub async fn get_network_fees(&self) -> eyre::Result<NetworkFees> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+            .await?
+            .ok_or_else(|| eyre::eyre!("The latest block is missing"))?;
+        let base_fee = block
+            .header
+            .base_fee_per_gas
+            .ok_or_else(|| eyre::eyre!("The latest block has no base fee"))?;
+
+        let eip1559_fees = self.provider.estimate_eip1559_fees(None).await?;
+
+        Ok(NetworkFees {
+            base_fee: base_fee as u128,
+            priority_fee: eip1559_fees.max_priority_fee_per_gas,
+        })
+    }
+
+    /// Estimated EIP-1559 fees, capped by the fee ceiling when one is set
+    async fn estimate_eip1559_fees(&self) -> eyre::Result<Eip1559Estimation> {
+        let eip1559_fees = self.provider.estimate_eip1559_fees(None).await?;
+
+        Ok(match &self.fee_ceiling {
+            Some(fee_ceiling) => fee_ceiling.apply(eip1559_fees),
+            None => eip1559_fees,
+        })
+    }
+
+    async fn build_transaction_request(
         &self,
         to: Address,
         input: Option<Bytes>,
         value: U256,
-    ) -> eyre::Result<bool> {
-        let eip1559_fees = self.provider.estimate_eip1559_fees(None).await?;
+    ) -> eyre::Result<TransactionRequest> {
+        let eip1559_fees = self.estimate_eip1559_fees().await?;
 
         let nonce = self.provider.get_transaction_count(self.signer.address()).await?;
 
@@ -74,9 +194,66 @@ where
         let gas_limit = self.provider.estimate_gas(&tx_request).await?;
         tx_request.set_gas_limit(gas_limit);
 
-        let signed_transaction = tx_request.build(&self.wallet).await?;
-        let pending_tx = self.provider.send_tx_envelope(signed_transaction).await?;
-        let receipt = pending_tx.get_receipt().await?;
+        Ok(tx_request)
+    }
+
+    /// Highest fee the transaction can cost in wei at the current gas price. The gas is estimated
+    /// without fees set, so the balance only has to cover `value`
+    pub async fn estimate_max_fee(
+        &self,
+        to: Address,
+        input: Option<Bytes>,
+        value: U256,
+    ) -> eyre::Result<U256> {
+        let eip1559_fees = self.estimate_eip1559_fees().await?;
+
+        let mut tx_request =
+            TransactionRequest::default().with_to(to).with_value(value).with_from(self.address());
+
+        if let Some(data) = input {
+            tx_request.set_input(data);
+        }
+
+        let gas_limit = self.provider.estimate_gas(&tx_request).await?;
+
+        Ok(U256::from(gas_limit) * U256::from(eip1559_fees.max_fee_per_gas))
+    }
+
+    pub async fn sign_transaction(
+        &self,
+        to: Address,
+        input: Option<Bytes>,
+        value: U256,
+    ) -> eyre::Result<SignedTransaction> {
+        let tx_request = self.build_transaction_request(to, input, value).await?;
+        let nonce = tx_request.nonce.expect("Nonce to be set by the request builder");
+        let envelope = tx_request.build(&self.wallet).await?;
+
+        Ok(SignedTransaction { hash: *envelope.tx_hash(), nonce, envelope })
+    }
+
+    pub async fn broadcast_transaction(&self, tx: SignedTransaction) -> eyre::Result<TxHash> {
+        let pending_tx = self.provider.send_tx_envelope(tx.envelope).await?;
+
+        Ok(*pending_tx.tx_hash())
+    }
+
+    /// Waits for the broadcast transaction to be mined. Returns `None` if it isn't mined within
+    /// `timeout`, it may still be mined later
+    pub async fn wait_for_transaction(
+        &self,
+        hash: TxHash,
+        timeout: Duration,
+    ) -> eyre::Result<Option<SentTransaction>> {
+        let receipt = match PendingTransactionBuilder::new(self.provider.root().clone(), hash)
+            .with_timeout(Some(timeout))
+            .get_receipt()
+            .await
+        {
+            Ok(receipt) => receipt,
+            Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
 
         let (_, url) = self.chain.etherscan_urls().unwrap_or(("", ""));
 
@@ -87,7 +264,27 @@ where
             tracing::error!("Transaction failed: {url}/tx/{}", receipt.transaction_hash);
         }
 
-        Ok(tx_status)
+        let fee = U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price);
+
+        Ok(Some(SentTransaction { hash: receipt.transaction_hash, success: tx_status, fee }))
+    }
+
+    /// Builds and signs the transaction, then runs it through `eth_call` without broadcasting
+    pub async fn simulate_transaction(
+        &self,
+        to: Address,
+        input: Option<Bytes>,
+        value: U256,
+    ) -> eyre::Result<TransactionSimulation> {
+        let tx_request = self.build_transaction_request(to, input, value).await?;
+
+        let gas_limit = tx_request.gas.unwrap_or_default();
+        let max_fee_per_gas = tx_request.max_fee_per_gas.unwrap_or_default();
+
+        tx_request.clone().build(&self.wallet).await?;
+        let output = self.provider.call(&tx_request).await?;
+
+        Ok(TransactionSimulation { gas_limit, max_fee_per_gas, output })
     }
 
     pub async fn get_token_balance(
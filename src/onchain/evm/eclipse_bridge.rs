@@ -1,14 +1,20 @@
+use std::sync::Arc;
+
 use alloy::{
+    contract::Error as ContractError,
     network::Ethereum,
     primitives::{FixedBytes, U256},
     providers::Provider,
     sol,
     sol_types::SolCall,
-    transports::Transport,
+    transports::{RpcError, Transport},
 };
 use solana_sdk::pubkey::Pubkey;
 
-use super::{client::EvmClient, constants::ECLIPSE_BRIDGE_CONTRACT_ADDRESS};
+use super::{
+    client::{EvmClient, SignedTransaction, TransactionSimulation},
+    constants::ECLIPSE_BRIDGE_CONTRACT_ADDRESS,
+};
 
 sol! {
     /// @inheritdoc ICanonicalBridge
@@ -24,12 +30,96 @@ sol! {
         nonReentrant;
 }
 
-/// Returns `Ok(true)` in case of a confirmed successful transaction
-pub async fn deposit<P, T>(
-    client: EvmClient<P, T>,
+sol! {
+    #[sol(rpc)]
+    contract CanonicalBridge {
+        function paused() external view returns (bool);
+        function MIN_DEPOSIT() external view returns (uint256);
+        function MAX_DEPOSIT() external view returns (uint256);
+    }
+}
+
+/// State of the bridge the `whenNotPaused` and `validDepositAmount` modifiers check
+pub struct BridgeLimits {
+    pub paused: bool,
+    /// Smallest deposit in wei
+    pub min_deposit: U256,
+    /// Largest deposit in wei, `None` when the bridge doesn't expose one
+    pub max_deposit: Option<U256>,
+}
+
+impl BridgeLimits {
+    /// Moves the amount into the allowed range
+    pub fn clamp(&self, amount: U256) -> U256 {
+        let amount = amount.max(self.min_deposit);
+
+        match self.max_deposit {
+            Some(max_deposit) => amount.min(max_deposit),
+            None => amount,
+        }
+    }
+}
+
+pub async fn get_bridge_limits<P, T>(provider: Arc<P>) -> eyre::Result<BridgeLimits>
+where
+    P: Provider<T, Ethereum>,
+    T: Transport + Clone,
+{
+    let bridge = CanonicalBridge::new(ECLIPSE_BRIDGE_CONTRACT_ADDRESS, provider);
+
+    let paused = bridge.paused().call().await?._0;
+    let min_deposit = bridge.MIN_DEPOSIT().call().await?._0;
+    let max_deposit = match bridge.MAX_DEPOSIT().call().await {
+        Ok(max_deposit) => Some(max_deposit._0),
+        // the call reverts or returns nothing when the bridge has no upper limit
+        Err(ContractError::TransportError(RpcError::ErrorResp(_)) | ContractError::AbiError(_)) => {
+            None
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(BridgeLimits { paused, min_deposit, max_deposit })
+}
+
+/// Signs a deposit without broadcasting it
+pub async fn sign_deposit<P, T>(
+    client: &EvmClient<P, T>,
+    recipient: Pubkey,
+    amount: U256,
+) -> eyre::Result<SignedTransaction>
+where
+    P: Provider<T, Ethereum>,
+    T: Transport + Clone,
+{
+    let recipient = FixedBytes::from_slice(&recipient.to_bytes());
+
+    let input = depositCall { recipient, amountWei: amount }.abi_encode();
+
+    client.sign_transaction(ECLIPSE_BRIDGE_CONTRACT_ADDRESS, Some(input.into()), amount).await
+}
+
+pub async fn simulate_deposit<P, T>(
+    client: &EvmClient<P, T>,
+    recipient: Pubkey,
+    amount: U256,
+) -> eyre::Result<TransactionSimulation>
+where
+    P: Provider<T, Ethereum>,
+    T: Transport + Clone,
+{
+    let recipient = FixedBytes::from_slice(&recipient.to_bytes());
+
+    let input = depositCall { recipient, amountWei: amount }.abi_encode();
+
+    client.simulate_transaction(ECLIPSE_BRIDGE_CONTRACT_ADDRESS, Some(input.into()), amount).await
+}
+
+/// Highest fee the deposit can cost in wei at the current gas price
+pub async fn estimate_deposit_fee<P, T>(
+    client: &EvmClient<P, T>,
     recipient: Pubkey,
     amount: U256,
-) -> eyre::Result<bool>
+) -> eyre::Result<U256>
 where
     P: Provider<T, Ethereum>,
     T: Transport + Clone,
@@ -38,5 +128,5 @@ where
 
     let input = depositCall { recipient, amountWei: amount }.abi_encode();
 
-    client.send_transaction(ECLIPSE_BRIDGE_CONTRACT_ADDRESS, Some(input.into()), amount).await
+    client.estimate_max_fee(ECLIPSE_BRIDGE_CONTRACT_ADDRESS, Some(input.into()), amount).await
 }
@@ -0,0 +1,87 @@
+use std::sync::OnceLock;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use argon2::Argon2;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use rand::RngCore;
+
+use super::secret::Secret;
+
+const ENCRYPTED_PREFIX: &str = "enc:";
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 16;
+
+static CIPHER: OnceLock<KeyCipher> = OnceLock::new();
+
+/// AES-256-GCM cipher keyed with an Argon2id hash of the user passphrase
+pub struct KeyCipher {
+    cipher: Aes256Gcm,
+}
+
+impl KeyCipher {
+    pub fn derive(passphrase: &str, salt: &[u8]) -> eyre::Result<Self> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| eyre::eyre!("Failed to derive the encryption key: {e}"))?;
+
+        Ok(Self { cipher: Aes256Gcm::new(&Key::<Aes256Gcm>::from(key)) })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> eyre::Result<String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| eyre::eyre!("Failed to encrypt: {e}"))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(format!("{ENCRYPTED_PREFIX}{}", BASE64_STANDARD.encode(payload)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> eyre::Result<Secret> {
+        let encoded = value
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or_else(|| eyre::eyre!("Value is not encrypted"))?;
+
+        let payload = BASE64_STANDARD.decode(encoded)?;
+        if payload.len() < NONCE_LEN {
+            eyre::bail!("Encrypted value is too short");
+        }
+
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce)?);
+        let plaintext = self
+            .cipher
+            .decrypt(&nonce, ciphertext)
+            .map_err(|_| eyre::eyre!("Failed to decrypt, the passphrase is wrong"))?;
+
+        Ok(Secret::new(String::from_utf8(plaintext)?))
+    }
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+pub fn generate_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+pub fn set_cipher(cipher: KeyCipher) {
+    if CIPHER.set(cipher).is_err() {
+        tracing::warn!("Encryption key is already set");
+    }
+}
+
+/// Cipher for the current run, set once the vault is unlocked
+pub fn cipher() -> eyre::Result<&'static KeyCipher> {
+    CIPHER.get().ok_or_else(|| eyre::eyre!("Private keys are locked, the vault was not unlocked"))
+}
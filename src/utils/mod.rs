@@ -1,4 +1,6 @@
+pub mod crypto;
 pub mod fetch;
 pub mod files;
 pub mod logger;
 pub mod misc;
+pub mod secret;
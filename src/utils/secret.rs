@@ -0,0 +1,44 @@
+use std::fmt;
+
+use sea_orm::DeriveValueType;
+use solana_program::hash::hash;
+
+/// Length of the hash prefix used to tell two secrets apart in logs
+const FINGERPRINT_LEN: usize = 8;
+
+/// Key material that is never printed. `Debug` and `Display` only show a short fingerprint of the
+/// value, the value itself is accessed with [`Secret::expose`]
+#[derive(Clone, PartialEq, Eq, DeriveValueType)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn fingerprint(&self) -> String {
+        hash(self.0.as_bytes()).to_string().chars().take(FINGERPRINT_LEN).collect()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", self.fingerprint())
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted {}>", self.fingerprint())
+    }
+}